@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// An arbitrary precision unsigned integer, stored as little-endian 64-bit
+/// words.
+///
+/// This is a plain value type mirroring SEAL's `BigUInt`; it crosses the FFI
+/// boundary as its words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUInt {
+    words: Vec<u64>,
+}
+
+impl BigUInt {
+    /// Creates an integer from its little-endian 64-bit words.
+    pub fn from_words(words: Vec<u64>) -> BigUInt {
+        let mut value = BigUInt { words };
+        value.normalize();
+        value
+    }
+
+    /// Returns the little-endian 64-bit words of the integer, without
+    /// leading zero words. Zero has no words.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the number of significant bits.
+    pub fn bit_count(&self) -> u32 {
+        match self.words.last() {
+            Some(&top) => 64 * (self.words.len() as u32 - 1) + (64 - top.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    /// Returns the value as a `u64`, or `None` if it does not fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.words.len() {
+            0 => Some(0),
+            1 => Some(self.words[0]),
+            _ => None,
+        }
+    }
+
+    /// Sets the value to `self * factor + addend`.
+    pub(crate) fn mul_add_small(&mut self, factor: u64, addend: u64) {
+        let mut carry = u128::from(addend);
+        for word in &mut self.words {
+            let product = u128::from(*word) * u128::from(factor) + carry;
+            *word = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 {
+            self.words.push(carry as u64);
+        }
+        self.normalize();
+    }
+
+    /// Divides the value by `divisor` in place and returns the remainder.
+    pub(crate) fn divrem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for word in self.words.iter_mut().rev() {
+            let current = (remainder << 64) | u128::from(*word);
+            *word = (current / u128::from(divisor)) as u64;
+            remainder = current % u128::from(divisor);
+        }
+        self.normalize();
+        remainder as u64
+    }
+
+    /// Returns `self - other`, or `None` if `other` is larger.
+    pub(crate) fn checked_sub(&self, other: &BigUInt) -> Option<BigUInt> {
+        if *self < *other {
+            return None;
+        }
+        let mut words = self.words.clone();
+        let mut borrow = false;
+        for (i, word) in words.iter_mut().enumerate() {
+            let rhs = other.words.get(i).cloned().unwrap_or(0);
+            let (diff, borrow1) = word.overflowing_sub(rhs);
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u64);
+            *word = diff;
+            borrow = borrow1 || borrow2;
+        }
+        Some(BigUInt::from_words(words))
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUInt {
+    fn from(value: u64) -> BigUInt {
+        BigUInt::from_words(vec![value])
+    }
+}
+
+impl Ord for BigUInt {
+    fn cmp(&self, other: &BigUInt) -> Ordering {
+        self.words
+            .len()
+            .cmp(&other.words.len())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUInt {
+    fn partial_cmp(&self, other: &BigUInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUInt {
+    /// Formats the value in upper-case hexadecimal, as SEAL's
+    /// `BigUInt::to_string` does.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.words.split_last() {
+            None => f.write_str("0"),
+            Some((top, rest)) => {
+                write!(f, "{:X}", top)?;
+                for word in rest.iter().rev() {
+                    write!(f, "{:016X}", word)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
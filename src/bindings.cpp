@@ -2,154 +2,1384 @@
 #include <stdexcept>
 #include <algorithm>
 #include <cmath>
+#include <complex>
+#include <functional>
+#include <ios>
+#include <limits>
+#include <memory>
+#include <new>
+#include <sstream>
+#include <string>
+#include <vector>
 #include "seal/seal.h"
+#include "seal/randomtostd.h"
+#include "seal/util/clipnormal.h"
+#include "seal/util/smallntt.h"
+#include "seal/util/uintarithsmallmod.h"
 
 using namespace seal;
 using namespace seal::util;
 
+namespace
+{
+    thread_local std::string last_error;
+
+    int fail(int status, const char* message) {
+        last_error = message;
+        return status;
+    }
+
+    // Runs f and translates any exception it throws into a status code, so
+    // that nothing unwinds across the extern "C" boundary.
+    template <typename F>
+    int guard(F f) {
+        try {
+            f();
+            return 0;
+        } catch (const std::invalid_argument& e) {
+            return fail(1, e.what());
+        } catch (const std::out_of_range& e) {
+            return fail(3, e.what());
+        } catch (const std::logic_error& e) {
+            return fail(2, e.what());
+        } catch (const std::ios_base::failure& e) {
+            return fail(4, e.what());
+        } catch (const std::runtime_error& e) {
+            return fail(5, e.what());
+        } catch (const std::bad_alloc& e) {
+            return fail(6, e.what());
+        } catch (const std::exception& e) {
+            return fail(7, e.what());
+        } catch (...) {
+            return fail(7, "unknown exception");
+        }
+    }
+}
+
 namespace bindings
 {
+    struct ContextHandle {
+        std::shared_ptr<SEALContext> ptr;
+    };
+
+    static parms_id_type to_parms_id(const uint64_t* parms_id) {
+        parms_id_type id;
+        std::copy(parms_id, parms_id + id.size(), id.begin());
+        return id;
+    }
+
+    static std::shared_ptr<const SEALContext::ContextData> context_data(ContextHandle* ctx, const uint64_t* parms_id) {
+        auto data = ctx->ptr->context_data(to_parms_id(parms_id));
+        if (!data) {
+            throw std::invalid_argument("parms_id is not in the modulus switching chain");
+        }
+        return data;
+    }
+
+    static void save_to_buffer(const std::function<void(std::ostream&)>& save, uint8_t** out, size_t* size) {
+        std::ostringstream stream(std::ios::binary);
+        stream.exceptions(std::ios_base::badbit | std::ios_base::failbit);
+        save(stream);
+        std::string bytes = stream.str();
+        uint8_t* copy = new uint8_t[bytes.size()];
+        std::copy(bytes.begin(), bytes.end(), copy);
+        *out = copy;
+        *size = bytes.size();
+    }
+
+    // Runs load on a stream over the buffer, which throws
+    // std::ios_base::failure if the buffer ends early.
+    static void load_from_buffer(const uint8_t* data, size_t size, const std::function<void(std::istream&)>& load) {
+        std::istringstream stream(std::string(reinterpret_cast<const char*>(data), size), std::ios::binary);
+        stream.exceptions(std::ios_base::badbit | std::ios_base::failbit);
+        load(stream);
+    }
+
+    struct SymmetricEncryptor {
+        std::shared_ptr<SEALContext> context;
+        SecretKey secret_key;
+    };
+
+    static uint64_t generate_uint64(UniformRandomGenerator& random) {
+        uint64_t high = random.generate();
+        uint64_t low = random.generate();
+        return (high << 32) | low;
+    }
+
+    // Throws unless poly has one coefficient per prime and slot of the level
+    // of data, each reduced modulo its prime.
+    static void check_poly(const SEALContext::ContextData& data, const uint64_t* poly, size_t count, const char* name) {
+        const std::vector<SmallModulus>& coeff_modulus = data.parms().coeff_modulus();
+        size_t coeff_count = data.parms().poly_modulus_degree();
+        if (count != coeff_count * coeff_modulus.size()) {
+            throw std::invalid_argument(std::string(name) + " does not have one coefficient per prime and slot of the level");
+        }
+        for (size_t j = 0; j < coeff_modulus.size(); j++) {
+            for (size_t i = 0; i < coeff_count; i++) {
+                if (poly[j * coeff_count + i] >= coeff_modulus[j].value()) {
+                    throw std::invalid_argument(std::string(name) + " is not reduced modulo the coefficient modulus");
+                }
+            }
+        }
+    }
+
+    const char* LastError_message() {
+        return last_error.c_str();
+    }
+
     // EncryptionParameters functions
-    EncryptionParameters* EncryptionParameters_Create(int scheme) {
-        return new EncryptionParameters((scheme_type)scheme);
+    int EncryptionParameters_Create(int scheme, EncryptionParameters** out) {
+        return guard([&] {
+            *out = new EncryptionParameters((scheme_type)scheme);
+        });
+    }
+
+    int EncryptionParameters_set_poly_modulus_degree(EncryptionParameters* ep, int degree) {
+        return guard([&] {
+            ep->set_poly_modulus_degree(degree);
+        });
     }
-    void EncryptionParameters_set_poly_modulus_degree(EncryptionParameters* ep, int degree) {
-        ep->set_poly_modulus_degree(degree);
+
+    int EncryptionParameters_set_coeff_modulus(EncryptionParameters* ep, int security_level, int degree) {
+        return guard([&] {
+            switch (security_level) {
+                case 128:
+                    ep->set_coeff_modulus(DefaultParams::coeff_modulus_128(degree));
+                    break;
+                case 192:
+                    ep->set_coeff_modulus(DefaultParams::coeff_modulus_192(degree));
+                    break;
+                case 256:
+                    ep->set_coeff_modulus(DefaultParams::coeff_modulus_256(degree));
+                    break;
+                default:
+                    throw std::invalid_argument("security_level must be 128, 192 or 256");
+            }
+        });
     }
-    void EncryptionParameters_set_coeff_modulus(EncryptionParameters* ep, int coeff, int degree) {
-        ep->set_coeff_modulus(DefaultParams::coeff_modulus_128(degree));
+
+    int EncryptionParameters_set_coeff_modulus_primes(EncryptionParameters* ep, const uint64_t* primes, size_t count) {
+        return guard([&] {
+            uint64_t two_n = 2 * static_cast<uint64_t>(ep->poly_modulus_degree());
+            if (two_n == 0) {
+                throw std::logic_error("poly_modulus_degree must be set before coeff_modulus");
+            }
+            std::vector<SmallModulus> coeff_modulus;
+            for (size_t i = 0; i < count; i++) {
+                if (primes[i] % two_n != 1) {
+                    throw std::invalid_argument("coeff_modulus prime " + std::to_string(primes[i])
+                        + " is not congruent to 1 modulo 2*poly_modulus_degree");
+                }
+                coeff_modulus.emplace_back(primes[i]);
+            }
+            ep->set_coeff_modulus(coeff_modulus);
+        });
     }
-    void EncryptionParameters_set_plain_modulus(EncryptionParameters* ep, int modulus) {
-        ep->set_plain_modulus(modulus);
+
+    int EncryptionParameters_set_plain_modulus(EncryptionParameters* ep, uint64_t modulus) {
+        return guard([&] {
+            ep->set_plain_modulus(modulus);
+        });
     }
-    const SmallModulus* EncryptionParameters_plain_modulus(EncryptionParameters* ep) {
-        return &ep->plain_modulus();
+
+    int EncryptionParameters_plain_modulus(EncryptionParameters* ep, uint64_t* out) {
+        return guard([&] {
+            *out = ep->plain_modulus().value();
+        });
+    }
+
+    int EncryptionParameters_scheme(const EncryptionParameters* ep, int* out) {
+        return guard([&] {
+            *out = static_cast<int>(ep->scheme());
+        });
+    }
+
+    int EncryptionParameters_poly_modulus_degree(const EncryptionParameters* ep, size_t* out) {
+        return guard([&] {
+            *out = ep->poly_modulus_degree();
+        });
+    }
+
+    int EncryptionParameters_coeff_modulus(const EncryptionParameters* ep, uint64_t* out, size_t* count) {
+        return guard([&] {
+            const std::vector<SmallModulus>& coeff_modulus = ep->coeff_modulus();
+            *count = coeff_modulus.size();
+            if (out) {
+                for (size_t i = 0; i < coeff_modulus.size(); i++) {
+                    out[i] = coeff_modulus[i].value();
+                }
+            }
+        });
+    }
+
+    int EncryptionParameters_noise_standard_deviation(const EncryptionParameters* ep, double* out) {
+        return guard([&] {
+            *out = ep->noise_standard_deviation();
+        });
+    }
+
+    int EncryptionParameters_noise_max_deviation(const EncryptionParameters* ep, double* out) {
+        return guard([&] {
+            *out = ep->noise_max_deviation();
+        });
+    }
+
+    int EncryptionParameters_parms_id(const EncryptionParameters* ep, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = ep->parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
+    }
+
+    void EncryptionParameters_Destroy(EncryptionParameters* ep) {
+        delete ep;
+    }
+
+    // DefaultParams functions
+    int DefaultParams_small_mods(int bit_size, size_t index, uint64_t* out) {
+        return guard([&] {
+            switch (bit_size) {
+                case 60:
+                    *out = DefaultParams::small_mods_60bit(index).value();
+                    break;
+                case 50:
+                    *out = DefaultParams::small_mods_50bit(index).value();
+                    break;
+                case 40:
+                    *out = DefaultParams::small_mods_40bit(index).value();
+                    break;
+                case 30:
+                    *out = DefaultParams::small_mods_30bit(index).value();
+                    break;
+                default:
+                    throw std::invalid_argument("bit_size must be 30, 40, 50 or 60");
+            }
+        });
+    }
+
+    int DefaultParams_dbc_min(int* out) {
+        return guard([&] {
+            *out = DefaultParams::dbc_min();
+        });
+    }
+
+    int DefaultParams_dbc_max(int* out) {
+        return guard([&] {
+            *out = DefaultParams::dbc_max();
+        });
     }
 
     // SEALContext functions
-    SEALContext* SEALContext_Create(const EncryptionParameters* parms, bool expand_mod_chain) {
-        return SEALContext::Create(*parms, expand_mod_chain);
+    int SEALContext_Create(const EncryptionParameters* parms, bool expand_mod_chain, ContextHandle** out) {
+        return guard([&] {
+            *out = new ContextHandle { SEALContext::Create(*parms, expand_mod_chain) };
+        });
+    }
+
+    int SEALContext_parameters_set(ContextHandle* ctx, bool* out) {
+        return guard([&] {
+            *out = ctx->ptr->context_data()->qualifiers().parameters_set;
+        });
+    }
+
+    int SEALContext_qualifiers(ContextHandle* ctx, EncryptionParameterQualifiers* out) {
+        return guard([&] {
+            *out = ctx->ptr->context_data()->qualifiers();
+        });
+    }
+
+    int SEALContext_total_coeff_modulus_bit_count(ContextHandle* ctx, int* out) {
+        return guard([&] {
+            *out = ctx->ptr->context_data()->total_coeff_modulus_bit_count();
+        });
+    }
+
+    int SEALContext_first_parms_id(ContextHandle* ctx, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = ctx->ptr->first_parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
     }
 
-    bool SEALContext_parameters_set(SEALContext* ctx) {
-        return ctx->context_data()->qualifiers().parameters_set;
+    int SEALContext_last_parms_id(ContextHandle* ctx, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = ctx->ptr->last_parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
+    }
+
+    void SEALContext_Destroy(ContextHandle* ctx) {
+        delete ctx;
+    }
+
+    // ContextData functions
+    int ContextData_chain_index(ContextHandle* ctx, const uint64_t* parms_id, size_t* out) {
+        return guard([&] {
+            *out = context_data(ctx, parms_id)->chain_index();
+        });
+    }
+
+    int ContextData_parms(ContextHandle* ctx, const uint64_t* parms_id, EncryptionParameters** out) {
+        return guard([&] {
+            *out = new EncryptionParameters(context_data(ctx, parms_id)->parms());
+        });
+    }
+
+    int ContextData_qualifiers(ContextHandle* ctx, const uint64_t* parms_id, EncryptionParameterQualifiers* out) {
+        return guard([&] {
+            *out = context_data(ctx, parms_id)->qualifiers();
+        });
+    }
+
+    int ContextData_total_coeff_modulus_bit_count(ContextHandle* ctx, const uint64_t* parms_id, int* out) {
+        return guard([&] {
+            *out = context_data(ctx, parms_id)->total_coeff_modulus_bit_count();
+        });
+    }
+
+    int ContextData_next_parms_id(ContextHandle* ctx, const uint64_t* parms_id, bool* has_next, uint64_t* out) {
+        return guard([&] {
+            auto next = context_data(ctx, parms_id)->next_context_data();
+            *has_next = static_cast<bool>(next);
+            if (next) {
+                const parms_id_type& next_parms_id = next->parms().parms_id();
+                std::copy(next_parms_id.begin(), next_parms_id.end(), out);
+            }
+        });
     }
 
     // IntegerEncoder functions
-    IntegerEncoder* IntegerEncoder_Create(SEALContext* ctx) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new IntegerEncoder(sp_ctx);
+    int IntegerEncoder_Create(ContextHandle* ctx, IntegerEncoder** out) {
+        return guard([&] {
+            *out = new IntegerEncoder(ctx->ptr);
+        });
+    }
+
+    int IntegerEncoder_encode_int64(IntegerEncoder* ie, int64_t value, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ie->encode(static_cast<std::int64_t>(value), *pt);
+            *out = pt.release();
+        });
+    }
+
+    int IntegerEncoder_encode_uint64(IntegerEncoder* ie, uint64_t value, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ie->encode(static_cast<std::uint64_t>(value), *pt);
+            *out = pt.release();
+        });
+    }
+
+    int IntegerEncoder_encode_biguint(IntegerEncoder* ie, const uint64_t* words, size_t count, Plaintext** out) {
+        return guard([&] {
+            BigUInt value(static_cast<int>(count * 64));
+            std::copy(words, words + count, value.data());
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ie->encode(value, *pt);
+            *out = pt.release();
+        });
     }
 
-    Plaintext* IntegerEncoder_encode(IntegerEncoder* ie, int value) {
-        Plaintext* pt = new Plaintext();
-        ie->encode(value, *pt);
-        return pt;
+    int IntegerEncoder_decode_int32(IntegerEncoder* ie, const Plaintext* pt, int* out) {
+        return guard([&] {
+            *out = ie->decode_int32(*pt);
+        });
     }
 
-    int IntegerEncoder_decode_int32(IntegerEncoder* ie, const Plaintext* pt) {
-        return ie->decode_int32(*pt);
+    int IntegerEncoder_decode_int64(IntegerEncoder* ie, const Plaintext* pt, int64_t* out) {
+        return guard([&] {
+            *out = ie->decode_int64(*pt);
+        });
+    }
+
+    int IntegerEncoder_decode_uint64(IntegerEncoder* ie, const Plaintext* pt, uint64_t* out) {
+        return guard([&] {
+            *out = ie->decode_uint64(*pt);
+        });
+    }
+
+    int IntegerEncoder_decode_biguint(IntegerEncoder* ie, const Plaintext* pt, uint64_t* out, size_t* count) {
+        return guard([&] {
+            BigUInt value = ie->decode_biguint(*pt);
+            *count = value.uint64_count();
+            if (out) {
+                std::copy(value.data(), value.data() + value.uint64_count(), out);
+            }
+        });
+    }
+
+    void IntegerEncoder_Destroy(IntegerEncoder* ie) {
+        delete ie;
     }
 
     // BatchEncoder functions
-    BatchEncoder* BatchEncoder_Create(SEALContext* ctx) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new BatchEncoder(sp_ctx);
+    int BatchEncoder_Create(ContextHandle* ctx, BatchEncoder** out) {
+        return guard([&] {
+            *out = new BatchEncoder(ctx->ptr);
+        });
+    }
+
+    int BatchEncoder_slot_count(BatchEncoder* be, size_t* out) {
+        return guard([&] {
+            *out = be->slot_count();
+        });
+    }
+
+    int BatchEncoder_encode_uint64(BatchEncoder* be, const uint64_t* values, size_t count, Plaintext** out) {
+        return guard([&] {
+            std::vector<std::uint64_t> values_matrix(values, values + count);
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            be->encode(values_matrix, *pt);
+            *out = pt.release();
+        });
     }
 
-    int BatchEncoder_slot_count(BatchEncoder* be) {
-        return be->slot_count();
+    int BatchEncoder_encode_int64(BatchEncoder* be, const int64_t* values, size_t count, Plaintext** out) {
+        return guard([&] {
+            std::vector<std::int64_t> values_matrix(values, values + count);
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            be->encode(values_matrix, *pt);
+            *out = pt.release();
+        });
+    }
+
+    int BatchEncoder_decode_uint64(BatchEncoder* be, const Plaintext* pt, uint64_t* out) {
+        return guard([&] {
+            std::vector<std::uint64_t> values_matrix;
+            be->decode(*pt, values_matrix);
+            std::copy(values_matrix.begin(), values_matrix.end(), out);
+        });
+    }
+
+    int BatchEncoder_decode_int64(BatchEncoder* be, const Plaintext* pt, int64_t* out) {
+        return guard([&] {
+            std::vector<std::int64_t> values_matrix;
+            be->decode(*pt, values_matrix);
+            std::copy(values_matrix.begin(), values_matrix.end(), out);
+        });
+    }
+
+    void BatchEncoder_Destroy(BatchEncoder* be) {
+        delete be;
+    }
+
+    // CKKSEncoder functions
+    int CKKSEncoder_Create(ContextHandle* ctx, CKKSEncoder** out) {
+        return guard([&] {
+            *out = new CKKSEncoder(ctx->ptr);
+        });
+    }
+
+    int CKKSEncoder_slot_count(CKKSEncoder* ce, size_t* out) {
+        return guard([&] {
+            *out = ce->slot_count();
+        });
+    }
+
+    int CKKSEncoder_encode_double(CKKSEncoder* ce, const double* values, size_t count, const uint64_t* parms_id, double scale, Plaintext** out) {
+        return guard([&] {
+            std::vector<double> input(values, values + count);
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ce->encode(input, to_parms_id(parms_id), scale, *pt);
+            *out = pt.release();
+        });
+    }
+
+    int CKKSEncoder_encode_complex(CKKSEncoder* ce, const double* values, size_t count, const uint64_t* parms_id, double scale, Plaintext** out) {
+        return guard([&] {
+            std::vector<std::complex<double>> input;
+            input.reserve(count);
+            for (size_t i = 0; i < count; i++) {
+                input.emplace_back(values[2 * i], values[2 * i + 1]);
+            }
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ce->encode(input, to_parms_id(parms_id), scale, *pt);
+            *out = pt.release();
+        });
+    }
+
+    int CKKSEncoder_encode_double_scalar(CKKSEncoder* ce, double value, const uint64_t* parms_id, double scale, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ce->encode(value, to_parms_id(parms_id), scale, *pt);
+            *out = pt.release();
+        });
+    }
+
+    int CKKSEncoder_encode_complex_scalar(CKKSEncoder* ce, double real, double imag, const uint64_t* parms_id, double scale, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            ce->encode(std::complex<double>(real, imag), to_parms_id(parms_id), scale, *pt);
+            *out = pt.release();
+        });
+    }
+
+    int CKKSEncoder_decode_double(CKKSEncoder* ce, const Plaintext* pt, double* out) {
+        return guard([&] {
+            std::vector<double> values;
+            ce->decode(*pt, values);
+            std::copy(values.begin(), values.end(), out);
+        });
+    }
+
+    int CKKSEncoder_decode_complex(CKKSEncoder* ce, const Plaintext* pt, double* out) {
+        return guard([&] {
+            std::vector<std::complex<double>> values;
+            ce->decode(*pt, values);
+            for (size_t i = 0; i < values.size(); i++) {
+                out[2 * i] = values[i].real();
+                out[2 * i + 1] = values[i].imag();
+            }
+        });
+    }
+
+    void CKKSEncoder_Destroy(CKKSEncoder* ce) {
+        delete ce;
     }
 
     // KeyGenerator functions
-    KeyGenerator* KeyGenerator_Create(SEALContext* ctx) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new KeyGenerator(sp_ctx);
+    int KeyGenerator_Create(ContextHandle* ctx, KeyGenerator** out) {
+        return guard([&] {
+            *out = new KeyGenerator(ctx->ptr);
+        });
+    }
+
+    int KeyGenerator_public_key(KeyGenerator* kg, PublicKey** out) {
+        return guard([&] {
+            *out = new PublicKey(kg->public_key());
+        });
+    }
+
+    int KeyGenerator_secret_key(KeyGenerator* kg, SecretKey** out) {
+        return guard([&] {
+            *out = new SecretKey(kg->secret_key());
+        });
+    }
+
+    int KeyGenerator_relin_keys(KeyGenerator* kg, int decomposition_bit_count, int count, RelinKeys** out) {
+        return guard([&] {
+            *out = new RelinKeys(kg->relin_keys(decomposition_bit_count, count));
+        });
+    }
+
+    int KeyGenerator_galois_keys(KeyGenerator* kg, int decomposition_bit_count, GaloisKeys** out) {
+        return guard([&] {
+            *out = new GaloisKeys(kg->galois_keys(decomposition_bit_count));
+        });
     }
 
-    const PublicKey* KeyGenerator_public_key(KeyGenerator* kg) {
-        return &kg->public_key();
+    int KeyGenerator_galois_keys_elts(KeyGenerator* kg, int decomposition_bit_count, const uint64_t* galois_elts, size_t count, GaloisKeys** out) {
+        return guard([&] {
+            std::vector<std::uint64_t> elts(galois_elts, galois_elts + count);
+            *out = new GaloisKeys(kg->galois_keys(decomposition_bit_count, elts));
+        });
     }
 
-    const SecretKey* KeyGenerator_secret_key(KeyGenerator* kg) {
-        return &kg->secret_key();
+    void KeyGenerator_Destroy(KeyGenerator* kg) {
+        delete kg;
     }
 
-    RelinKeys* KeyGenerator_relin_keys(KeyGenerator* kg, int decomposition_bit_count, int count) {
-        return new RelinKeys(kg->relin_keys(decomposition_bit_count, count));
+    // Key functions
+    void PublicKey_Destroy(PublicKey* pk) {
+        delete pk;
     }
 
-    GaloisKeys* KeyGenerator_galois_keys(KeyGenerator* kg, int decomposition_bit_count) {
-        return new GaloisKeys(kg->galois_keys(decomposition_bit_count));
+    void SecretKey_Destroy(SecretKey* sk) {
+        delete sk;
+    }
+
+    int RelinKeys_size(const RelinKeys* rk, size_t* out) {
+        return guard([&] {
+            *out = rk->size();
+        });
+    }
+
+    int RelinKeys_decomposition_bit_count(const RelinKeys* rk, int* out) {
+        return guard([&] {
+            *out = rk->decomposition_bit_count();
+        });
+    }
+
+    int RelinKeys_parms_id(const RelinKeys* rk, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = rk->parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
+    }
+
+    void RelinKeys_Destroy(RelinKeys* rk) {
+        delete rk;
+    }
+
+    int GaloisKeys_has_key(const GaloisKeys* gk, uint64_t galois_elt, bool* out) {
+        return guard([&] {
+            *out = gk->has_key(galois_elt);
+        });
+    }
+
+    void GaloisKeys_Destroy(GaloisKeys* gk) {
+        delete gk;
     }
 
     // Evaluator functions
-    Evaluator* Evaluator_Create(SEALContext* ctx) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new Evaluator(sp_ctx);
+    int Evaluator_Create(ContextHandle* ctx, Evaluator** out) {
+        return guard([&] {
+            *out = new Evaluator(ctx->ptr);
+        });
+    }
+
+    int Evaluator_negate_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->negate_inplace(*c1);
+        });
+    }
+
+    int Evaluator_add_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2) {
+        return guard([&] {
+            evr->add_inplace(*c1, *c2);
+        });
+    }
+
+    int Evaluator_multiply_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2) {
+        return guard([&] {
+            evr->multiply_inplace(*c1, *c2);
+        });
+    }
+
+    int Evaluator_square_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->square_inplace(*c1);
+        });
+    }
+
+    int Evaluator_relinearize_inplace(Evaluator* evr, Ciphertext* c1, const RelinKeys* rk) {
+        return guard([&] {
+            evr->relinearize_inplace(*c1, *rk);
+        });
+    }
+
+    int Evaluator_sub_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2) {
+        return guard([&] {
+            evr->sub_inplace(*c1, *c2);
+        });
+    }
+
+    int Evaluator_add_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt) {
+        return guard([&] {
+            evr->add_plain_inplace(*c1, *pt);
+        });
+    }
+
+    int Evaluator_sub_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt) {
+        return guard([&] {
+            evr->sub_plain_inplace(*c1, *pt);
+        });
+    }
+
+    int Evaluator_multiply_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt) {
+        return guard([&] {
+            evr->multiply_plain_inplace(*c1, *pt);
+        });
+    }
+
+    int Evaluator_negate(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->negate(*c1, *ct);
+            *out = ct.release();
+        });
     }
 
-    void Evaluator_negate_inplace(Evaluator* evr, Ciphertext* c1) {
-        evr->negate_inplace(*c1);
+    int Evaluator_add(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->add(*c1, *c2, *ct);
+            *out = ct.release();
+        });
     }
 
-    void Evaluator_add_inplace(Evaluator* evr, Ciphertext* c1, Ciphertext* c2) {
-        evr->add_inplace(*c1, *c2);
+    int Evaluator_sub(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->sub(*c1, *c2, *ct);
+            *out = ct.release();
+        });
     }
 
-    void Evaluator_multiply_inplace(Evaluator* evr, Ciphertext* c1, Ciphertext* c2) {
-        evr->multiply_inplace(*c1, *c2);
+    int Evaluator_multiply(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->multiply(*c1, *c2, *ct);
+            *out = ct.release();
+        });
     }
 
-    void Evaluator_square_inplace(Evaluator* evr, Ciphertext* c1) {
-        evr->square_inplace(*c1);
+    int Evaluator_square(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->square(*c1, *ct);
+            *out = ct.release();
+        });
     }
 
-    void Evaluator_relinearize_inplace(Evaluator* evr, Ciphertext* c1, RelinKeys* rk) {
-        evr->relinearize_inplace(*c1, *rk);
+    int Evaluator_relinearize(Evaluator* evr, const Ciphertext* c1, const RelinKeys* rk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->relinearize(*c1, *rk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_relinearize_to(Evaluator* evr, const Ciphertext* c1, const RelinKeys* rk, size_t destination_size, Ciphertext** out) {
+        return guard([&] {
+            if (destination_size < 2 || destination_size > c1->size()) {
+                throw std::invalid_argument("destination_size must be at least 2 and at most the ciphertext size");
+            }
+            // SEAL only relinearizes all the way down to size 2. Relinearizing
+            // the components from destination_size upwards on their own folds
+            // them into the first two, which is exactly what SEAL's internal
+            // step-by-step relinearization adds to the lower components.
+            std::unique_ptr<Ciphertext> ct(new Ciphertext(*c1));
+            if (destination_size < c1->size()) {
+                Ciphertext high(*c1);
+                std::size_t poly_uint64_count = c1->poly_modulus_degree() * c1->coeff_mod_count();
+                for (std::size_t i = 0; i < destination_size; i++) {
+                    std::fill_n(high.data(i), poly_uint64_count, 0);
+                }
+                evr->relinearize_inplace(high, *rk);
+                ct->resize(destination_size);
+                evr->add_inplace(*ct, high);
+            }
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_add_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->add_plain(*c1, *pt, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_sub_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->sub_plain(*c1, *pt, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_multiply_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->multiply_plain(*c1, *pt, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_add_many(Evaluator* evr, const Ciphertext** cts, size_t count, Ciphertext** out) {
+        return guard([&] {
+            std::vector<Ciphertext> encrypteds;
+            encrypteds.reserve(count);
+            for (size_t i = 0; i < count; i++) {
+                encrypteds.push_back(*cts[i]);
+            }
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->add_many(encrypteds, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_multiply_many(Evaluator* evr, const Ciphertext** cts, size_t count, const RelinKeys* rk, Ciphertext** out) {
+        return guard([&] {
+            std::vector<Ciphertext> encrypteds;
+            encrypteds.reserve(count);
+            for (size_t i = 0; i < count; i++) {
+                encrypteds.push_back(*cts[i]);
+            }
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->multiply_many(encrypteds, *rk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_exponentiate_inplace(Evaluator* evr, Ciphertext* c1, uint64_t exponent, const RelinKeys* rk) {
+        return guard([&] {
+            evr->exponentiate_inplace(*c1, exponent, *rk);
+        });
+    }
+
+    int Evaluator_exponentiate(Evaluator* evr, const Ciphertext* c1, uint64_t exponent, const RelinKeys* rk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->exponentiate(*c1, exponent, *rk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_rotate_rows_inplace(Evaluator* evr, Ciphertext* c1, int steps, const GaloisKeys* gk) {
+        return guard([&] {
+            evr->rotate_rows_inplace(*c1, steps, *gk);
+        });
+    }
+
+    int Evaluator_rotate_rows(Evaluator* evr, const Ciphertext* c1, int steps, const GaloisKeys* gk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->rotate_rows(*c1, steps, *gk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_rotate_columns_inplace(Evaluator* evr, Ciphertext* c1, const GaloisKeys* gk) {
+        return guard([&] {
+            evr->rotate_columns_inplace(*c1, *gk);
+        });
+    }
+
+    int Evaluator_rotate_columns(Evaluator* evr, const Ciphertext* c1, const GaloisKeys* gk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->rotate_columns(*c1, *gk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_rotate_vector_inplace(Evaluator* evr, Ciphertext* c1, int steps, const GaloisKeys* gk) {
+        return guard([&] {
+            evr->rotate_vector_inplace(*c1, steps, *gk);
+        });
+    }
+
+    int Evaluator_rotate_vector(Evaluator* evr, const Ciphertext* c1, int steps, const GaloisKeys* gk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->rotate_vector(*c1, steps, *gk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_complex_conjugate_inplace(Evaluator* evr, Ciphertext* c1, const GaloisKeys* gk) {
+        return guard([&] {
+            evr->complex_conjugate_inplace(*c1, *gk);
+        });
+    }
+
+    int Evaluator_complex_conjugate(Evaluator* evr, const Ciphertext* c1, const GaloisKeys* gk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->complex_conjugate(*c1, *gk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_apply_galois_inplace(Evaluator* evr, Ciphertext* c1, uint64_t galois_elt, const GaloisKeys* gk) {
+        return guard([&] {
+            evr->apply_galois_inplace(*c1, galois_elt, *gk);
+        });
+    }
+
+    int Evaluator_apply_galois(Evaluator* evr, const Ciphertext* c1, uint64_t galois_elt, const GaloisKeys* gk, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->apply_galois(*c1, galois_elt, *gk, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_mod_switch_to_next_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->mod_switch_to_next_inplace(*c1);
+        });
+    }
+
+    int Evaluator_mod_switch_to_next(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> result(new Ciphertext());
+            evr->mod_switch_to_next(*c1, *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_mod_switch_to_inplace(Evaluator* evr, Ciphertext* c1, const uint64_t* parms_id) {
+        return guard([&] {
+            evr->mod_switch_to_inplace(*c1, to_parms_id(parms_id));
+        });
+    }
+
+    int Evaluator_mod_switch_to(Evaluator* evr, const Ciphertext* c1, const uint64_t* parms_id, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> result(new Ciphertext());
+            evr->mod_switch_to(*c1, to_parms_id(parms_id), *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_mod_switch_plain_to_next_inplace(Evaluator* evr, Plaintext* pt) {
+        return guard([&] {
+            evr->mod_switch_to_next_inplace(*pt);
+        });
+    }
+
+    int Evaluator_mod_switch_plain_to_next(Evaluator* evr, const Plaintext* pt, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> result(new Plaintext());
+            evr->mod_switch_to_next(*pt, *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_mod_switch_plain_to_inplace(Evaluator* evr, Plaintext* pt, const uint64_t* parms_id) {
+        return guard([&] {
+            evr->mod_switch_to_inplace(*pt, to_parms_id(parms_id));
+        });
+    }
+
+    int Evaluator_mod_switch_plain_to(Evaluator* evr, const Plaintext* pt, const uint64_t* parms_id, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> result(new Plaintext());
+            evr->mod_switch_to(*pt, to_parms_id(parms_id), *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_rescale_to_next_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->rescale_to_next_inplace(*c1);
+        });
+    }
+
+    int Evaluator_rescale_to_next(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> result(new Ciphertext());
+            evr->rescale_to_next(*c1, *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_rescale_to_inplace(Evaluator* evr, Ciphertext* c1, const uint64_t* parms_id) {
+        return guard([&] {
+            evr->rescale_to_inplace(*c1, to_parms_id(parms_id));
+        });
+    }
+
+    int Evaluator_rescale_to(Evaluator* evr, const Ciphertext* c1, const uint64_t* parms_id, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> result(new Ciphertext());
+            evr->rescale_to(*c1, to_parms_id(parms_id), *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_transform_plain_to_ntt_inplace(Evaluator* evr, Plaintext* pt, const uint64_t* parms_id) {
+        return guard([&] {
+            evr->transform_to_ntt_inplace(*pt, to_parms_id(parms_id));
+        });
+    }
+
+    int Evaluator_transform_plain_to_ntt(Evaluator* evr, const Plaintext* pt, const uint64_t* parms_id, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> result(new Plaintext());
+            evr->transform_to_ntt(*pt, to_parms_id(parms_id), *result);
+            *out = result.release();
+        });
+    }
+
+    int Evaluator_transform_to_ntt_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->transform_to_ntt_inplace(*c1);
+        });
+    }
+
+    int Evaluator_transform_to_ntt(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->transform_to_ntt(*c1, *ct);
+            *out = ct.release();
+        });
+    }
+
+    int Evaluator_transform_from_ntt_inplace(Evaluator* evr, Ciphertext* c1) {
+        return guard([&] {
+            evr->transform_from_ntt_inplace(*c1);
+        });
+    }
+
+    int Evaluator_transform_from_ntt(Evaluator* evr, const Ciphertext* c1, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            evr->transform_from_ntt(*c1, *ct);
+            *out = ct.release();
+        });
+    }
+
+    void Evaluator_Destroy(Evaluator* evr) {
+        delete evr;
     }
 
     // Encryptor functions
-    Encryptor* Encryptor_Create(SEALContext* ctx, const PublicKey* pk) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new Encryptor(sp_ctx, *pk);
+    int Encryptor_Create(ContextHandle* ctx, const PublicKey* pk, Encryptor** out) {
+        return guard([&] {
+            *out = new Encryptor(ctx->ptr, *pk);
+        });
     }
 
-    Ciphertext* Encryptor_encrypt(Encryptor* enc, Plaintext* pt) {
-        Ciphertext* ct = new Ciphertext();
-        enc->encrypt(*pt, *ct);
-        return ct;
+    int Encryptor_encrypt(Encryptor* enc, const Plaintext* pt, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            enc->encrypt(*pt, *ct);
+            *out = ct.release();
+        });
+    }
+
+    void Encryptor_Destroy(Encryptor* enc) {
+        delete enc;
+    }
+
+    // SymmetricEncryptor functions
+    int SymmetricEncryptor_Create(ContextHandle* ctx, const SecretKey* sk, SymmetricEncryptor** out) {
+        return guard([&] {
+            if (!sk->is_valid_for(ctx->ptr)) {
+                throw std::invalid_argument("secret key is not valid for encryption parameters");
+            }
+            *out = new SymmetricEncryptor{ ctx->ptr, *sk };
+        });
+    }
+
+    int SymmetricEncryptor_draw_seed(SymmetricEncryptor* enc, uint64_t* seed) {
+        return guard([&] {
+            std::shared_ptr<UniformRandomGenerator> random(enc->context->context_data()->parms().random_generator()->create());
+            for (size_t i = 0; i < 4; i++) {
+                seed[i] = generate_uint64(*random);
+            }
+        });
+    }
+
+    int SymmetricEncryptor_encrypt(SymmetricEncryptor* enc, const Plaintext* pt, const uint64_t* c1, size_t count, Ciphertext** out) {
+        return guard([&] {
+            // BFV encrypts at the first level and CKKS at the level of the
+            // plaintext, as Encryptor does.
+            auto first = enc->context->context_data();
+            bool ckks = first->parms().scheme() == scheme_type::CKKS;
+            auto data = ckks ? enc->context->context_data(pt->parms_id()) : first;
+            if (!data) {
+                throw std::invalid_argument("plain is not valid for encryption parameters");
+            }
+            check_poly(*data, c1, count, "c1");
+            const EncryptionParameters& parms = data->parms();
+            const std::vector<SmallModulus>& coeff_modulus = parms.coeff_modulus();
+            size_t coeff_count = parms.poly_modulus_degree();
+
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            ct->resize(enc->context, parms.parms_id(), 2);
+            ct->is_ntt_form() = true;
+            std::copy_n(c1, count, ct->data(1));
+
+            // c0 = e - c1 * s in NTT form, with the secret key, which is stored
+            // in NTT form at the first level, truncated to the primes of this
+            // level.
+            std::shared_ptr<UniformRandomGenerator> random(parms.random_generator()->create());
+            RandomToStandardAdapter engine(random);
+            ClippedNormalDistribution dist(0, parms.noise_standard_deviation(), parms.noise_max_deviation());
+            uint64_t* c0 = ct->data(0);
+            const uint64_t* s = enc->secret_key.data().data();
+            for (size_t i = 0; i < coeff_count; i++) {
+                int64_t noise = static_cast<int64_t>(dist(engine));
+                uint64_t magnitude = static_cast<uint64_t>(noise < 0 ? -noise : noise);
+                for (size_t j = 0; j < coeff_modulus.size(); j++) {
+                    c0[j * coeff_count + i] = noise < 0 ? negate_uint_mod(magnitude, coeff_modulus[j]) : magnitude;
+                }
+            }
+            for (size_t j = 0; j < coeff_modulus.size(); j++) {
+                ntt_negacyclic_harvey(c0 + j * coeff_count, data->small_ntt_tables()[j]);
+                for (size_t i = 0; i < coeff_count; i++) {
+                    size_t k = j * coeff_count + i;
+                    c0[k] = sub_uint_uint_mod(c0[k], multiply_uint_uint_mod(c1[k], s[k], coeff_modulus[j]), coeff_modulus[j]);
+                }
+            }
+
+            if (ckks) {
+                ct->scale() = pt->scale();
+            } else {
+                for (size_t j = 0; j < coeff_modulus.size(); j++) {
+                    inverse_ntt_negacyclic_harvey(ct->data(0) + j * coeff_count, data->small_ntt_tables()[j]);
+                    inverse_ntt_negacyclic_harvey(ct->data(1) + j * coeff_count, data->small_ntt_tables()[j]);
+                }
+                ct->is_ntt_form() = false;
+            }
+            // Adding the plaintext to the encryption of zero scales it by
+            // q / t for BFV, exactly as public key encryption does.
+            Evaluator(enc->context).add_plain_inplace(*ct, *pt);
+            *out = ct.release();
+        });
+    }
+
+    void SymmetricEncryptor_Destroy(SymmetricEncryptor* enc) {
+        delete enc;
     }
 
     // Decryptor functions
-    Decryptor* Decryptor_Create(SEALContext* ctx, const SecretKey* sk) {
-        std::shared_ptr<SEALContext> sp_ctx(static_cast<SEALContext*>(ctx));
-        return new Decryptor(sp_ctx, *sk);
+    int Decryptor_Create(ContextHandle* ctx, const SecretKey* sk, Decryptor** out) {
+        return guard([&] {
+            *out = new Decryptor(ctx->ptr, *sk);
+        });
+    }
+
+    int Decryptor_decrypt(Decryptor* dec, const Ciphertext* c1, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            dec->decrypt(*c1, *pt);
+            *out = pt.release();
+        });
     }
 
-    Plaintext* Decryptor_decrypt(Decryptor* dec, Ciphertext* c1) {
-        Plaintext* pt = new Plaintext();
-        dec->decrypt(*c1, *pt);
-        return pt;
+    int Decryptor_invariant_noise_budget(Decryptor* dec, const Ciphertext* ct, int* out) {
+        return guard([&] {
+            *out = dec->invariant_noise_budget(*ct);
+        });
     }
 
-    int Decryptor_invariant_noise_budget(Decryptor* dec, Ciphertext* ct) {
-        return dec->invariant_noise_budget(*ct);
+    void Decryptor_Destroy(Decryptor* dec) {
+        delete dec;
     }
 
     // Plaintext functions
-    Plaintext* Plaintext_Create(const char* hex_poly) {
-        std::string str_hex_poly(hex_poly);
-        return new Plaintext(str_hex_poly);
+    int Plaintext_Create(const char* hex_poly, Plaintext** out) {
+        return guard([&] {
+            std::string str_hex_poly(hex_poly);
+            *out = new Plaintext(str_hex_poly);
+        });
+    }
+
+    int Plaintext_to_string(const Plaintext* pt, char** out) {
+        return guard([&] {
+            std::string str = pt->to_string();
+            char* copy = new char[str.size() + 1];
+            std::copy(str.begin(), str.end(), copy);
+            copy[str.size()] = '\0';
+            *out = copy;
+        });
+    }
+
+    int Plaintext_Create_coeffs(const uint64_t* coeffs, size_t count, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext(count));
+            std::copy(coeffs, coeffs + count, pt->data());
+            *out = pt.release();
+        });
     }
 
-    const char* Plaintext_to_string(Plaintext* pt) {
-        return pt->to_string().c_str();
+    int Plaintext_coeffs(const Plaintext* pt, uint64_t* out, size_t* count) {
+        return guard([&] {
+            *count = pt->coeff_count();
+            if (out) {
+                std::copy(pt->data(), pt->data() + pt->coeff_count(), out);
+            }
+        });
+    }
+
+    int Plaintext_parms_id(const Plaintext* pt, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = pt->parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
+    }
+
+    int Plaintext_scale(const Plaintext* pt, double* out) {
+        return guard([&] {
+            *out = pt->scale();
+        });
+    }
+
+    int Plaintext_is_ntt_form(const Plaintext* pt, bool* out) {
+        return guard([&] {
+            *out = pt->is_ntt_form();
+        });
+    }
+
+    void Plaintext_Destroy(Plaintext* pt) {
+        delete pt;
     }
 
     // Ciphertext functions
-    int Ciphertext_size(const Ciphertext* ct1) {
-        return ct1->size();
+    int Ciphertext_size(const Ciphertext* ct1, int* out) {
+        return guard([&] {
+            *out = ct1->size();
+        });
+    }
+
+    int Ciphertext_parms_id(const Ciphertext* ct, uint64_t* out) {
+        return guard([&] {
+            const parms_id_type& parms_id = ct->parms_id();
+            std::copy(parms_id.begin(), parms_id.end(), out);
+        });
+    }
+
+    int Ciphertext_scale(const Ciphertext* ct, double* out) {
+        return guard([&] {
+            *out = ct->scale();
+        });
+    }
+
+    int Ciphertext_set_scale(Ciphertext* ct, double scale) {
+        return guard([&] {
+            ct->scale() = scale;
+        });
+    }
+
+    int Ciphertext_is_ntt_form(const Ciphertext* ct, bool* out) {
+        return guard([&] {
+            *out = ct->is_ntt_form();
+        });
+    }
+
+    int Ciphertext_poly(const Ciphertext* ct, size_t poly_index, uint64_t* out, size_t* count) {
+        return guard([&] {
+            if (poly_index >= ct->size()) {
+                throw std::out_of_range("poly_index must be less than the ciphertext size");
+            }
+            *count = ct->poly_modulus_degree() * ct->coeff_mod_count();
+            if (out) {
+                std::copy_n(ct->data(poly_index), *count, out);
+            }
+        });
+    }
+
+    int Ciphertext_expand_seeded(ContextHandle* ctx, const uint64_t* parms_id, const uint64_t* c0, const uint64_t* c1, size_t count, double scale, Ciphertext** out) {
+        return guard([&] {
+            auto data = context_data(ctx, parms_id);
+            check_poly(*data, c0, count, "c0");
+            check_poly(*data, c1, count, "c1");
+            const std::vector<SmallModulus>& coeff_modulus = data->parms().coeff_modulus();
+            size_t coeff_count = data->parms().poly_modulus_degree();
+            bool ckks = data->parms().scheme() == scheme_type::CKKS;
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            ct->resize(ctx->ptr, data->parms().parms_id(), 2);
+            ct->is_ntt_form() = ckks;
+            ct->scale() = ckks ? scale : 1.0;
+            std::copy_n(c0, count, ct->data(0));
+            std::copy_n(c1, count, ct->data(1));
+            if (!ckks) {
+                for (size_t j = 0; j < coeff_modulus.size(); j++) {
+                    inverse_ntt_negacyclic_harvey(ct->data(1) + j * coeff_count, data->small_ntt_tables()[j]);
+                }
+            }
+            *out = ct.release();
+        });
+    }
+
+    void Ciphertext_Destroy(Ciphertext* ct) {
+        delete ct;
+    }
+
+    // Serialization functions
+    int EncryptionParameters_save(const EncryptionParameters* ep, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { EncryptionParameters::Save(*ep, stream); }, out, size);
+        });
+    }
+
+    int EncryptionParameters_load(const uint8_t* data, size_t size, EncryptionParameters** out) {
+        return guard([&] {
+            load_from_buffer(data, size, [&](std::istream& stream) {
+                *out = new EncryptionParameters(EncryptionParameters::Load(stream));
+            });
+        });
+    }
+
+    int Ciphertext_save(const Ciphertext* ct, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { ct->save(stream); }, out, size);
+        });
+    }
+
+    int Ciphertext_load(ContextHandle* ctx, const uint8_t* data, size_t size, Ciphertext** out) {
+        return guard([&] {
+            std::unique_ptr<Ciphertext> ct(new Ciphertext());
+            load_from_buffer(data, size, [&](std::istream& stream) { ct->load(ctx->ptr, stream); });
+            *out = ct.release();
+        });
+    }
+
+    int Plaintext_save(const Plaintext* pt, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { pt->save(stream); }, out, size);
+        });
+    }
+
+    int Plaintext_load(ContextHandle* ctx, const uint8_t* data, size_t size, Plaintext** out) {
+        return guard([&] {
+            std::unique_ptr<Plaintext> pt(new Plaintext());
+            load_from_buffer(data, size, [&](std::istream& stream) { pt->load(ctx->ptr, stream); });
+            *out = pt.release();
+        });
+    }
+
+    int PublicKey_save(const PublicKey* pk, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { pk->save(stream); }, out, size);
+        });
     }
-}
 
+    int PublicKey_load(ContextHandle* ctx, const uint8_t* data, size_t size, PublicKey** out) {
+        return guard([&] {
+            std::unique_ptr<PublicKey> pk(new PublicKey());
+            load_from_buffer(data, size, [&](std::istream& stream) { pk->load(ctx->ptr, stream); });
+            *out = pk.release();
+        });
+    }
+
+    int SecretKey_save(const SecretKey* sk, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { sk->save(stream); }, out, size);
+        });
+    }
+
+    int SecretKey_load(ContextHandle* ctx, const uint8_t* data, size_t size, SecretKey** out) {
+        return guard([&] {
+            std::unique_ptr<SecretKey> sk(new SecretKey());
+            load_from_buffer(data, size, [&](std::istream& stream) { sk->load(ctx->ptr, stream); });
+            *out = sk.release();
+        });
+    }
+
+    int RelinKeys_save(const RelinKeys* rk, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { rk->save(stream); }, out, size);
+        });
+    }
+
+    int RelinKeys_load(ContextHandle* ctx, const uint8_t* data, size_t size, RelinKeys** out) {
+        return guard([&] {
+            std::unique_ptr<RelinKeys> rk(new RelinKeys());
+            load_from_buffer(data, size, [&](std::istream& stream) { rk->load(ctx->ptr, stream); });
+            *out = rk.release();
+        });
+    }
+
+    int GaloisKeys_save(const GaloisKeys* gk, uint8_t** out, size_t* size) {
+        return guard([&] {
+            save_to_buffer([&](std::ostream& stream) { gk->save(stream); }, out, size);
+        });
+    }
+
+    int GaloisKeys_load(ContextHandle* ctx, const uint8_t* data, size_t size, GaloisKeys** out) {
+        return guard([&] {
+            std::unique_ptr<GaloisKeys> gk(new GaloisKeys());
+            load_from_buffer(data, size, [&](std::istream& stream) { gk->load(ctx->ptr, stream); });
+            *out = gk.release();
+        });
+    }
+
+    // String functions
+    void String_Destroy(char* str) {
+        delete[] str;
+    }
+
+    // Byte buffer functions
+    void Bytes_Destroy(uint8_t* bytes) {
+        delete[] bytes;
+    }
+}
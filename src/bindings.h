@@ -4,55 +4,239 @@ using namespace seal;
 
 namespace bindings
 {
+  // Owns one reference to a SEALContext for as long as the Rust side holds it
+  struct ContextHandle;
+
+  // Encrypts with a copy of a secret key, see SymmetricEncryptor functions
+  struct SymmetricEncryptor;
+
+  // Every function below except the *_Destroy ones catches the exceptions
+  // thrown by SEAL and returns a status code, with results written through
+  // the trailing out parameters:
+  //   0 success, 1 std::invalid_argument, 2 std::logic_error,
+  //   3 std::out_of_range, 4 std::ios_base::failure, 5 std::runtime_error,
+  //   6 std::bad_alloc, 7 anything else.
+  // The message of the last exception caught on the calling thread can be
+  // read with LastError_message.
+  extern "C" const char* LastError_message();
+
   // EncryptionParameters functions
-  extern "C" EncryptionParameters* EncryptionParameters_Create(int scheme);
-  extern "C" void EncryptionParameters_set_poly_modulus_degree(EncryptionParameters* ep, int degree);
-  extern "C" void EncryptionParameters_set_coeff_modulus(EncryptionParameters* ep, int coeff, int degree);
-  extern "C" void EncryptionParameters_set_plain_modulus(EncryptionParameters* ep, int modulus);
-  extern "C" const SmallModulus* EncryptionParameters_plain_modulus(EncryptionParameters* ep);
+  extern "C" int EncryptionParameters_Create(int scheme, EncryptionParameters** out);
+  extern "C" int EncryptionParameters_set_poly_modulus_degree(EncryptionParameters* ep, int degree);
+  extern "C" int EncryptionParameters_set_coeff_modulus(EncryptionParameters* ep, int security_level, int degree);
+  extern "C" int EncryptionParameters_set_coeff_modulus_primes(EncryptionParameters* ep, const uint64_t* primes, size_t count);
+  extern "C" int EncryptionParameters_set_plain_modulus(EncryptionParameters* ep, uint64_t modulus);
+  extern "C" int EncryptionParameters_plain_modulus(EncryptionParameters* ep, uint64_t* out);
+  extern "C" int EncryptionParameters_scheme(const EncryptionParameters* ep, int* out);
+  extern "C" int EncryptionParameters_poly_modulus_degree(const EncryptionParameters* ep, size_t* out);
+  // Writes the number of primes to count, and the primes themselves to out unless it is null
+  extern "C" int EncryptionParameters_coeff_modulus(const EncryptionParameters* ep, uint64_t* out, size_t* count);
+  extern "C" int EncryptionParameters_noise_standard_deviation(const EncryptionParameters* ep, double* out);
+  extern "C" int EncryptionParameters_noise_max_deviation(const EncryptionParameters* ep, double* out);
+  // Writes the four 64-bit words of the parms_id to out
+  extern "C" int EncryptionParameters_parms_id(const EncryptionParameters* ep, uint64_t* out);
+  extern "C" void EncryptionParameters_Destroy(EncryptionParameters* ep);
+
+  // DefaultParams functions
+  extern "C" int DefaultParams_small_mods(int bit_size, size_t index, uint64_t* out);
+  extern "C" int DefaultParams_dbc_min(int* out);
+  extern "C" int DefaultParams_dbc_max(int* out);
 
   // SEALContext functions
-  extern "C" SEALContext* SEALContext_Create(const EncryptionParameters* parms, bool expand_mod_chain);
-  extern "C" bool SEALContext_parameters_set(void* ctx);
+  extern "C" int SEALContext_Create(const EncryptionParameters* parms, bool expand_mod_chain, ContextHandle** out);
+  extern "C" int SEALContext_parameters_set(ContextHandle* ctx, bool* out);
+  extern "C" int SEALContext_qualifiers(ContextHandle* ctx, EncryptionParameterQualifiers* out);
+  extern "C" int SEALContext_total_coeff_modulus_bit_count(ContextHandle* ctx, int* out);
+  extern "C" int SEALContext_first_parms_id(ContextHandle* ctx, uint64_t* out);
+  extern "C" int SEALContext_last_parms_id(ContextHandle* ctx, uint64_t* out);
+  extern "C" void SEALContext_Destroy(ContextHandle* ctx);
+
+  // ContextData functions, each looking up the level of the modulus switching
+  // chain whose parms_id is given as four 64-bit words
+  extern "C" int ContextData_chain_index(ContextHandle* ctx, const uint64_t* parms_id, size_t* out);
+  extern "C" int ContextData_parms(ContextHandle* ctx, const uint64_t* parms_id, EncryptionParameters** out);
+  extern "C" int ContextData_qualifiers(ContextHandle* ctx, const uint64_t* parms_id, EncryptionParameterQualifiers* out);
+  extern "C" int ContextData_total_coeff_modulus_bit_count(ContextHandle* ctx, const uint64_t* parms_id, int* out);
+  // Writes whether there is a next level to has_next, and its parms_id to out if there is
+  extern "C" int ContextData_next_parms_id(ContextHandle* ctx, const uint64_t* parms_id, bool* has_next, uint64_t* out);
 
   // IntegerEncoder functions
-  extern "C" IntegerEncoder* IntegerEncoder_Create(SEALContext* ctx);
-  extern "C" Plaintext* IntegerEncoder_encode(IntegerEncoder* ie, int value);
-  extern "C" int IntegerEncoder_decode_int32(IntegerEncoder* ie, const Plaintext* pt);
+  extern "C" int IntegerEncoder_Create(ContextHandle* ctx, IntegerEncoder** out);
+  extern "C" int IntegerEncoder_encode_int64(IntegerEncoder* ie, int64_t value, Plaintext** out);
+  extern "C" int IntegerEncoder_encode_uint64(IntegerEncoder* ie, uint64_t value, Plaintext** out);
+  // words points to the count little-endian 64-bit words of the value
+  extern "C" int IntegerEncoder_encode_biguint(IntegerEncoder* ie, const uint64_t* words, size_t count, Plaintext** out);
+  extern "C" int IntegerEncoder_decode_int32(IntegerEncoder* ie, const Plaintext* pt, int* out);
+  extern "C" int IntegerEncoder_decode_int64(IntegerEncoder* ie, const Plaintext* pt, int64_t* out);
+  extern "C" int IntegerEncoder_decode_uint64(IntegerEncoder* ie, const Plaintext* pt, uint64_t* out);
+  // Writes the number of words to count, and the words themselves to out unless it is null
+  extern "C" int IntegerEncoder_decode_biguint(IntegerEncoder* ie, const Plaintext* pt, uint64_t* out, size_t* count);
+  extern "C" void IntegerEncoder_Destroy(IntegerEncoder* ie);
 
   // BatchEncoder functions
-  extern "C" BatchEncoder* BatchEncoder_Create(SEALContext* ctx);
-  extern "C" BatchEncoder* BatchEncoder_slot_count(BatchENcoder* be);
+  extern "C" int BatchEncoder_Create(ContextHandle* ctx, BatchEncoder** out);
+  extern "C" int BatchEncoder_slot_count(BatchEncoder* be, size_t* out);
+  extern "C" int BatchEncoder_encode_uint64(BatchEncoder* be, const uint64_t* values, size_t count, Plaintext** out);
+  extern "C" int BatchEncoder_encode_int64(BatchEncoder* be, const int64_t* values, size_t count, Plaintext** out);
+  // out must have room for slot_count values
+  extern "C" int BatchEncoder_decode_uint64(BatchEncoder* be, const Plaintext* pt, uint64_t* out);
+  extern "C" int BatchEncoder_decode_int64(BatchEncoder* be, const Plaintext* pt, int64_t* out);
+  extern "C" void BatchEncoder_Destroy(BatchEncoder* be);
+
+  // CKKSEncoder functions, complex numbers being passed as interleaved real
+  // and imaginary parts, and parms_id as four 64-bit words
+  extern "C" int CKKSEncoder_Create(ContextHandle* ctx, CKKSEncoder** out);
+  extern "C" int CKKSEncoder_slot_count(CKKSEncoder* ce, size_t* out);
+  extern "C" int CKKSEncoder_encode_double(CKKSEncoder* ce, const double* values, size_t count, const uint64_t* parms_id, double scale, Plaintext** out);
+  extern "C" int CKKSEncoder_encode_complex(CKKSEncoder* ce, const double* values, size_t count, const uint64_t* parms_id, double scale, Plaintext** out);
+  extern "C" int CKKSEncoder_encode_double_scalar(CKKSEncoder* ce, double value, const uint64_t* parms_id, double scale, Plaintext** out);
+  extern "C" int CKKSEncoder_encode_complex_scalar(CKKSEncoder* ce, double real, double imag, const uint64_t* parms_id, double scale, Plaintext** out);
+  // out must have room for slot_count values, or twice that for complex numbers
+  extern "C" int CKKSEncoder_decode_double(CKKSEncoder* ce, const Plaintext* pt, double* out);
+  extern "C" int CKKSEncoder_decode_complex(CKKSEncoder* ce, const Plaintext* pt, double* out);
+  extern "C" void CKKSEncoder_Destroy(CKKSEncoder* ce);
 
   // KeyGenerator functions
-  extern "C" KeyGenerator* KeyGenerator_Create(SEALContext* ctx);
-  extern "C" const PublicKey* KeyGenerator_public_key(KeyGenerator* kg);
-  extern "C" const SecretKey* KeyGenerator_secret_key(KeyGenerator* kg);
-  extern "C" RelinKeys* KeyGenerator_relin_keys(KeyGenerator* kg, int decomposition_bit_count, int count);
-  extern "C" GaloisKeys* KeyGenerator_galois_keys(KeyGenerator* kg, int decomposition_bit_count);
+  extern "C" int KeyGenerator_Create(ContextHandle* ctx, KeyGenerator** out);
+  extern "C" int KeyGenerator_public_key(KeyGenerator* kg, PublicKey** out);
+  extern "C" int KeyGenerator_secret_key(KeyGenerator* kg, SecretKey** out);
+  extern "C" int KeyGenerator_relin_keys(KeyGenerator* kg, int decomposition_bit_count, int count, RelinKeys** out);
+  extern "C" int KeyGenerator_galois_keys(KeyGenerator* kg, int decomposition_bit_count, GaloisKeys** out);
+  extern "C" int KeyGenerator_galois_keys_elts(KeyGenerator* kg, int decomposition_bit_count, const uint64_t* galois_elts, size_t count, GaloisKeys** out);
+  extern "C" void KeyGenerator_Destroy(KeyGenerator* kg);
+
+  // Key functions
+  extern "C" void PublicKey_Destroy(PublicKey* pk);
+  extern "C" void SecretKey_Destroy(SecretKey* sk);
+  extern "C" int RelinKeys_size(const RelinKeys* rk, size_t* out);
+  extern "C" int RelinKeys_decomposition_bit_count(const RelinKeys* rk, int* out);
+  extern "C" int RelinKeys_parms_id(const RelinKeys* rk, uint64_t* out);
+  extern "C" void RelinKeys_Destroy(RelinKeys* rk);
+  extern "C" int GaloisKeys_has_key(const GaloisKeys* gk, uint64_t galois_elt, bool* out);
+  extern "C" void GaloisKeys_Destroy(GaloisKeys* gk);
 
   // Evaluator functions
-  extern "C" Evaluator* Evaluator_Create(SEALContext* ctx);
-  extern "C" void Evaluator_negate_inplace(Evaluator* evr, Ciphertext* c1);
-  extern "C" void Evaluator_add_inplace(Evaluator* evr, Ciphertext* c1, Ciphertext* c2);
-  extern "C" void Evaluator_multiply_inplace(Evaluator* evr, Ciphertext* c1, Ciphertext* c2);
-  extern "C" void Evaluator_square_inplace(Evaluator* evr, Ciphertext* c1);
-  extern "C" void Evaluator_relinearize_inplace(Evaluator* evr, Ciphertext* c1, RelinKeys* rk);
+  extern "C" int Evaluator_Create(ContextHandle* ctx, Evaluator** out);
+  extern "C" int Evaluator_negate_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_add_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2);
+  extern "C" int Evaluator_multiply_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2);
+  extern "C" int Evaluator_square_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_relinearize_inplace(Evaluator* evr, Ciphertext* c1, const RelinKeys* rk);
+  extern "C" int Evaluator_sub_inplace(Evaluator* evr, Ciphertext* c1, const Ciphertext* c2);
+  extern "C" int Evaluator_add_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt);
+  extern "C" int Evaluator_sub_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt);
+  extern "C" int Evaluator_multiply_plain_inplace(Evaluator* evr, Ciphertext* c1, const Plaintext* pt);
+  extern "C" int Evaluator_negate(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" int Evaluator_add(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out);
+  extern "C" int Evaluator_sub(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out);
+  extern "C" int Evaluator_multiply(Evaluator* evr, const Ciphertext* c1, const Ciphertext* c2, Ciphertext** out);
+  extern "C" int Evaluator_square(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" int Evaluator_relinearize(Evaluator* evr, const Ciphertext* c1, const RelinKeys* rk, Ciphertext** out);
+  // Relinearizes c1 down to destination_size polynomials, which must be at least 2
+  extern "C" int Evaluator_relinearize_to(Evaluator* evr, const Ciphertext* c1, const RelinKeys* rk, size_t destination_size, Ciphertext** out);
+  extern "C" int Evaluator_add_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out);
+  extern "C" int Evaluator_sub_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out);
+  extern "C" int Evaluator_multiply_plain(Evaluator* evr, const Ciphertext* c1, const Plaintext* pt, Ciphertext** out);
+  // cts points to count ciphertexts, which are copied before SEAL sees them
+  extern "C" int Evaluator_add_many(Evaluator* evr, const Ciphertext** cts, size_t count, Ciphertext** out);
+  extern "C" int Evaluator_multiply_many(Evaluator* evr, const Ciphertext** cts, size_t count, const RelinKeys* rk, Ciphertext** out);
+  extern "C" int Evaluator_exponentiate_inplace(Evaluator* evr, Ciphertext* c1, uint64_t exponent, const RelinKeys* rk);
+  extern "C" int Evaluator_exponentiate(Evaluator* evr, const Ciphertext* c1, uint64_t exponent, const RelinKeys* rk, Ciphertext** out);
+  extern "C" int Evaluator_rotate_rows_inplace(Evaluator* evr, Ciphertext* c1, int steps, const GaloisKeys* gk);
+  extern "C" int Evaluator_rotate_rows(Evaluator* evr, const Ciphertext* c1, int steps, const GaloisKeys* gk, Ciphertext** out);
+  extern "C" int Evaluator_rotate_columns_inplace(Evaluator* evr, Ciphertext* c1, const GaloisKeys* gk);
+  extern "C" int Evaluator_rotate_columns(Evaluator* evr, const Ciphertext* c1, const GaloisKeys* gk, Ciphertext** out);
+  extern "C" int Evaluator_rotate_vector_inplace(Evaluator* evr, Ciphertext* c1, int steps, const GaloisKeys* gk);
+  extern "C" int Evaluator_rotate_vector(Evaluator* evr, const Ciphertext* c1, int steps, const GaloisKeys* gk, Ciphertext** out);
+  extern "C" int Evaluator_complex_conjugate_inplace(Evaluator* evr, Ciphertext* c1, const GaloisKeys* gk);
+  extern "C" int Evaluator_complex_conjugate(Evaluator* evr, const Ciphertext* c1, const GaloisKeys* gk, Ciphertext** out);
+  extern "C" int Evaluator_apply_galois_inplace(Evaluator* evr, Ciphertext* c1, uint64_t galois_elt, const GaloisKeys* gk);
+  extern "C" int Evaluator_apply_galois(Evaluator* evr, const Ciphertext* c1, uint64_t galois_elt, const GaloisKeys* gk, Ciphertext** out);
+  extern "C" int Evaluator_mod_switch_to_next_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_mod_switch_to_next(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" int Evaluator_mod_switch_to_inplace(Evaluator* evr, Ciphertext* c1, const uint64_t* parms_id);
+  extern "C" int Evaluator_mod_switch_to(Evaluator* evr, const Ciphertext* c1, const uint64_t* parms_id, Ciphertext** out);
+  extern "C" int Evaluator_mod_switch_plain_to_next_inplace(Evaluator* evr, Plaintext* pt);
+  extern "C" int Evaluator_mod_switch_plain_to_next(Evaluator* evr, const Plaintext* pt, Plaintext** out);
+  extern "C" int Evaluator_mod_switch_plain_to_inplace(Evaluator* evr, Plaintext* pt, const uint64_t* parms_id);
+  extern "C" int Evaluator_mod_switch_plain_to(Evaluator* evr, const Plaintext* pt, const uint64_t* parms_id, Plaintext** out);
+  extern "C" int Evaluator_rescale_to_next_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_rescale_to_next(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" int Evaluator_rescale_to_inplace(Evaluator* evr, Ciphertext* c1, const uint64_t* parms_id);
+  extern "C" int Evaluator_rescale_to(Evaluator* evr, const Ciphertext* c1, const uint64_t* parms_id, Ciphertext** out);
+  extern "C" int Evaluator_transform_plain_to_ntt_inplace(Evaluator* evr, Plaintext* pt, const uint64_t* parms_id);
+  extern "C" int Evaluator_transform_plain_to_ntt(Evaluator* evr, const Plaintext* pt, const uint64_t* parms_id, Plaintext** out);
+  extern "C" int Evaluator_transform_to_ntt_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_transform_to_ntt(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" int Evaluator_transform_from_ntt_inplace(Evaluator* evr, Ciphertext* c1);
+  extern "C" int Evaluator_transform_from_ntt(Evaluator* evr, const Ciphertext* c1, Ciphertext** out);
+  extern "C" void Evaluator_Destroy(Evaluator* evr);
 
   // Encryptor functions
-  extern "C" Encryptor* Encryptor_Create(SEALContext* ctx, const PublicKey* pk);
-  extern "C" Ciphertext* Encryptor_encrypt(Encryptor* enc, Plaintext* pt);
+  extern "C" int Encryptor_Create(ContextHandle* ctx, const PublicKey* pk, Encryptor** out);
+  extern "C" int Encryptor_encrypt(Encryptor* enc, const Plaintext* pt, Ciphertext** out);
+  extern "C" void Encryptor_Destroy(Encryptor* enc);
+
+  // SymmetricEncryptor functions. The caller expands the second polynomial of
+  // every ciphertext from a fresh 256-bit seed, so that it can be dropped and
+  // regenerated with Ciphertext_expand_seeded
+  extern "C" int SymmetricEncryptor_Create(ContextHandle* ctx, const SecretKey* sk, SymmetricEncryptor** out);
+  // Draws a seed of four words from the random generator of the encryption parameters
+  extern "C" int SymmetricEncryptor_draw_seed(SymmetricEncryptor* enc, uint64_t* seed);
+  // Encrypts pt with c1, given in NTT form at the level of the encryption, as the second polynomial
+  extern "C" int SymmetricEncryptor_encrypt(SymmetricEncryptor* enc, const Plaintext* pt, const uint64_t* c1, size_t count, Ciphertext** out);
+  extern "C" void SymmetricEncryptor_Destroy(SymmetricEncryptor* enc);
 
   // Decryptor functions
-  extern "C" Decryptor* Decryptor_Create(SEALContext* ctx, const SecretKey* sk);
-  extern "C" Plaintext* Decryptor_decrypt(Decryptor* dec, Ciphertext* c1);
-  extern "C" int Decryptor_invariant_noise_budget(Decryptor* dec, Ciphertext* ct);
+  extern "C" int Decryptor_Create(ContextHandle* ctx, const SecretKey* sk, Decryptor** out);
+  extern "C" int Decryptor_decrypt(Decryptor* dec, const Ciphertext* c1, Plaintext** out);
+  extern "C" int Decryptor_invariant_noise_budget(Decryptor* dec, const Ciphertext* ct, int* out);
+  extern "C" void Decryptor_Destroy(Decryptor* dec);
 
   // Plaintext functions
-  extern "C" Plaintext* Plaintext_Create(const char* hex_poly);
-  extern "C" const char* Plaintext_to_string(Plaintext* pt);
+  extern "C" int Plaintext_Create(const char* hex_poly, Plaintext** out);
+  extern "C" int Plaintext_to_string(const Plaintext* pt, char** out);
+  extern "C" int Plaintext_Create_coeffs(const uint64_t* coeffs, size_t count, Plaintext** out);
+  // Writes the number of coefficients to count, and the coefficients themselves to out unless it is null
+  extern "C" int Plaintext_coeffs(const Plaintext* pt, uint64_t* out, size_t* count);
+  extern "C" int Plaintext_parms_id(const Plaintext* pt, uint64_t* out);
+  extern "C" int Plaintext_scale(const Plaintext* pt, double* out);
+  extern "C" int Plaintext_is_ntt_form(const Plaintext* pt, bool* out);
+  extern "C" void Plaintext_Destroy(Plaintext* pt);
 
   // Ciphertext functions
-  extern "C" int Ciphertext_size(const Ciphertext* ct1);
+  extern "C" int Ciphertext_size(const Ciphertext* ct1, int* out);
+  extern "C" int Ciphertext_parms_id(const Ciphertext* ct, uint64_t* out);
+  extern "C" int Ciphertext_scale(const Ciphertext* ct, double* out);
+  extern "C" int Ciphertext_set_scale(Ciphertext* ct, double scale);
+  extern "C" int Ciphertext_is_ntt_form(const Ciphertext* ct, bool* out);
+  // Writes the number of words in polynomial poly_index to count, and the words themselves to out unless it is null
+  extern "C" int Ciphertext_poly(const Ciphertext* ct, size_t poly_index, uint64_t* out, size_t* count);
+  // Rebuilds a ciphertext made by SymmetricEncryptor_encrypt from its first polynomial and c1 in NTT form
+  extern "C" int Ciphertext_expand_seeded(ContextHandle* ctx, const uint64_t* parms_id, const uint64_t* c0, const uint64_t* c1, size_t count, double scale, Ciphertext** out);
+  extern "C" void Ciphertext_Destroy(Ciphertext* ct);
+
+  // Serialization functions. *_save writes a newly allocated buffer holding
+  // the SEAL stream to out, to be released with Bytes_Destroy. *_load reads
+  // the stream back and checks that the object is valid for the context.
+  extern "C" int EncryptionParameters_save(const EncryptionParameters* ep, uint8_t** out, size_t* size);
+  extern "C" int EncryptionParameters_load(const uint8_t* data, size_t size, EncryptionParameters** out);
+  extern "C" int Ciphertext_save(const Ciphertext* ct, uint8_t** out, size_t* size);
+  extern "C" int Ciphertext_load(ContextHandle* ctx, const uint8_t* data, size_t size, Ciphertext** out);
+  extern "C" int Plaintext_save(const Plaintext* pt, uint8_t** out, size_t* size);
+  extern "C" int Plaintext_load(ContextHandle* ctx, const uint8_t* data, size_t size, Plaintext** out);
+  extern "C" int PublicKey_save(const PublicKey* pk, uint8_t** out, size_t* size);
+  extern "C" int PublicKey_load(ContextHandle* ctx, const uint8_t* data, size_t size, PublicKey** out);
+  extern "C" int SecretKey_save(const SecretKey* sk, uint8_t** out, size_t* size);
+  extern "C" int SecretKey_load(ContextHandle* ctx, const uint8_t* data, size_t size, SecretKey** out);
+  extern "C" int RelinKeys_save(const RelinKeys* rk, uint8_t** out, size_t* size);
+  extern "C" int RelinKeys_load(ContextHandle* ctx, const uint8_t* data, size_t size, RelinKeys** out);
+  extern "C" int GaloisKeys_save(const GaloisKeys* gk, uint8_t** out, size_t* size);
+  extern "C" int GaloisKeys_load(ContextHandle* ctx, const uint8_t* data, size_t size, GaloisKeys** out);
+
+  // String functions
+  extern "C" void String_Destroy(char* str);
 
+  // Byte buffer functions
+  extern "C" void Bytes_Destroy(uint8_t* bytes);
 }
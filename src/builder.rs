@@ -0,0 +1,248 @@
+use context::Context;
+use error::{Error, Result};
+use modulus::SmallModulus;
+use parameters::{EncryptionParameters, SchemeType, SecurityLevel};
+
+/// The largest total bit count of the coefficient modulus that the
+/// HomomorphicEncryption.org security standard allows at 128, 192 and 256-bit
+/// security, for each polynomial modulus degree it covers.
+const MAX_COEFF_MODULUS_BIT_COUNTS: [(usize, [u32; 3]); 6] = [
+    (1024, [27, 19, 14]),
+    (2048, [54, 37, 29]),
+    (4096, [109, 75, 58]),
+    (8192, [218, 152, 118]),
+    (16384, [438, 305, 237]),
+    (32768, [881, 611, 476]),
+];
+
+/// Builds BFV encryption parameters and the `Context` validating them.
+///
+/// Created with `EncryptionParameters::bfv()`. The polynomial modulus degree
+/// and the plain modulus must be set; the coefficient modulus defaults to
+/// SEAL's secure one for the chosen degree and security level (128-bit unless
+/// set).
+#[derive(Debug, Clone)]
+pub struct BfvParametersBuilder {
+    poly_modulus_degree: Option<usize>,
+    security_level: SecurityLevel,
+    coeff_modulus: Option<Vec<SmallModulus>>,
+    plain_modulus: Option<u64>,
+    batching: bool,
+    expand_mod_chain: bool,
+}
+
+/// Builds CKKS encryption parameters and the `Context` validating them.
+///
+/// Created with `EncryptionParameters::ckks()`. The polynomial modulus degree
+/// must be set; the coefficient modulus defaults to SEAL's secure one for the
+/// chosen degree and security level (128-bit unless set). CKKS has no plain
+/// modulus, so there is no setter for it.
+#[derive(Debug, Clone)]
+pub struct CkksParametersBuilder {
+    poly_modulus_degree: Option<usize>,
+    security_level: SecurityLevel,
+    coeff_modulus: Option<Vec<SmallModulus>>,
+    expand_mod_chain: bool,
+}
+
+impl BfvParametersBuilder {
+    pub(crate) fn new() -> BfvParametersBuilder {
+        BfvParametersBuilder {
+            poly_modulus_degree: None,
+            security_level: SecurityLevel::Tc128,
+            coeff_modulus: None,
+            plain_modulus: None,
+            batching: false,
+            expand_mod_chain: true,
+        }
+    }
+
+    pub fn poly_modulus_degree(mut self, degree: usize) -> BfvParametersBuilder {
+        self.poly_modulus_degree = Some(degree);
+        self
+    }
+
+    /// Targets the given security level. Without custom primes the
+    /// coefficient modulus is SEAL's default for it; with them, building fails
+    /// if they have more bits in total than that default.
+    pub fn security_level(mut self, security_level: SecurityLevel) -> BfvParametersBuilder {
+        self.security_level = security_level;
+        self
+    }
+
+    /// Uses the product of the given primes as the coefficient modulus. Their
+    /// total bit count is checked against the security level when building.
+    pub fn coeff_modulus(mut self, primes: &[SmallModulus]) -> BfvParametersBuilder {
+        self.coeff_modulus = Some(primes.to_vec());
+        self
+    }
+
+    /// Sets the plain modulus. It is checked to be a valid `SmallModulus`
+    /// when building.
+    pub fn plain_modulus(mut self, modulus: u64) -> BfvParametersBuilder {
+        self.plain_modulus = Some(modulus);
+        self
+    }
+
+    /// Makes building fail unless the plain modulus supports batching.
+    pub fn batching(mut self, batching: bool) -> BfvParametersBuilder {
+        self.batching = batching;
+        self
+    }
+
+    /// Whether the context should build the modulus switching chain. Defaults
+    /// to true, as in SEAL.
+    pub fn expand_mod_chain(mut self, expand_mod_chain: bool) -> BfvParametersBuilder {
+        self.expand_mod_chain = expand_mod_chain;
+        self
+    }
+
+    /// Returns the encryption parameters without validating them.
+    pub fn parameters(&self) -> Result<EncryptionParameters> {
+        let plain_modulus = match self.plain_modulus {
+            Some(modulus) => SmallModulus::new(modulus)?,
+            None => return Err(Error::InvalidArgument("plain_modulus is not set".to_owned())),
+        };
+        let mut parms =
+            parameters(SchemeType::Bfv, self.poly_modulus_degree, self.security_level, &self.coeff_modulus)?;
+        parms.set_plain_modulus(plain_modulus)?;
+        Ok(parms)
+    }
+
+    /// Creates the context for these parameters, returning
+    /// `Error::InvalidArgument` naming every qualifier SEAL did not set, or if
+    /// the coefficient modulus is too large for the security level.
+    pub fn build(&self) -> Result<Context> {
+        let parms = self.parameters()?;
+        let ctx = Context::new(&parms, self.expand_mod_chain)?;
+        validate(&ctx, &parms, self.batching, self.security_level)?;
+        Ok(ctx)
+    }
+}
+
+impl CkksParametersBuilder {
+    pub(crate) fn new() -> CkksParametersBuilder {
+        CkksParametersBuilder {
+            poly_modulus_degree: None,
+            security_level: SecurityLevel::Tc128,
+            coeff_modulus: None,
+            expand_mod_chain: true,
+        }
+    }
+
+    pub fn poly_modulus_degree(mut self, degree: usize) -> CkksParametersBuilder {
+        self.poly_modulus_degree = Some(degree);
+        self
+    }
+
+    /// Targets the given security level. Without custom primes the
+    /// coefficient modulus is SEAL's default for it; with them, building fails
+    /// if they have more bits in total than that default.
+    pub fn security_level(mut self, security_level: SecurityLevel) -> CkksParametersBuilder {
+        self.security_level = security_level;
+        self
+    }
+
+    /// Uses the product of the given primes as the coefficient modulus. Their
+    /// total bit count is checked against the security level when building.
+    pub fn coeff_modulus(mut self, primes: &[SmallModulus]) -> CkksParametersBuilder {
+        self.coeff_modulus = Some(primes.to_vec());
+        self
+    }
+
+    /// Whether the context should build the modulus switching chain. Defaults
+    /// to true, as in SEAL.
+    pub fn expand_mod_chain(mut self, expand_mod_chain: bool) -> CkksParametersBuilder {
+        self.expand_mod_chain = expand_mod_chain;
+        self
+    }
+
+    /// Returns the encryption parameters without validating them.
+    pub fn parameters(&self) -> Result<EncryptionParameters> {
+        parameters(SchemeType::Ckks, self.poly_modulus_degree, self.security_level, &self.coeff_modulus)
+    }
+
+    /// Creates the context for these parameters, returning
+    /// `Error::InvalidArgument` naming every qualifier SEAL did not set, or if
+    /// the coefficient modulus is too large for the security level.
+    pub fn build(&self) -> Result<Context> {
+        let parms = self.parameters()?;
+        let ctx = Context::new(&parms, self.expand_mod_chain)?;
+        validate(&ctx, &parms, false, self.security_level)?;
+        Ok(ctx)
+    }
+}
+
+fn parameters(
+    scheme: SchemeType,
+    poly_modulus_degree: Option<usize>,
+    security_level: SecurityLevel,
+    coeff_modulus: &Option<Vec<SmallModulus>>,
+) -> Result<EncryptionParameters> {
+    let degree = match poly_modulus_degree {
+        Some(degree) if degree <= i32::MAX as usize => degree as i32,
+        Some(degree) => return Err(Error::InvalidArgument(format!("poly_modulus_degree {} is too large", degree))),
+        None => return Err(Error::InvalidArgument("poly_modulus_degree is not set".to_owned())),
+    };
+    let mut parms = EncryptionParameters::new(scheme)?;
+    parms.set_poly_modulus_degree(degree)?;
+    match *coeff_modulus {
+        Some(ref primes) => parms.set_coeff_modulus_primes(primes)?,
+        None => parms.set_coeff_modulus(security_level, degree)?,
+    }
+    Ok(parms)
+}
+
+fn validate(
+    ctx: &Context,
+    parms: &EncryptionParameters,
+    batching: bool,
+    security_level: SecurityLevel,
+) -> Result<()> {
+    let degree = parms.poly_modulus_degree()?;
+    let max_bit_count = max_coeff_modulus_bit_count(degree, security_level)?;
+    let qualifiers = ctx.qualifiers()?;
+    let mut failed = Vec::new();
+    for &(name, set) in &[
+        ("parameters_set", qualifiers.parameters_set),
+        ("using_fft", qualifiers.using_fft),
+        ("using_ntt", qualifiers.using_ntt),
+        ("using_batching", qualifiers.using_batching || !batching),
+        ("using_he_std_security", qualifiers.using_he_std_security),
+    ] {
+        if !set {
+            failed.push(name);
+        }
+    }
+    if !failed.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "encryption parameters failed validation: {} not set",
+            failed.join(", ")
+        )));
+    }
+
+    // using_he_std_security only covers 128-bit security.
+    let bit_count = parms.coeff_modulus_bit_count()?;
+    if bit_count > max_bit_count {
+        return Err(Error::InvalidArgument(format!(
+            "coefficient modulus has {} bits, more than the {} bits allowed for {:?} at degree {}",
+            bit_count, max_bit_count, security_level, degree
+        )));
+    }
+    Ok(())
+}
+
+fn max_coeff_modulus_bit_count(degree: usize, security_level: SecurityLevel) -> Result<u32> {
+    let column = match security_level {
+        SecurityLevel::Tc128 => 0,
+        SecurityLevel::Tc192 => 1,
+        SecurityLevel::Tc256 => 2,
+    };
+    match MAX_COEFF_MODULUS_BIT_COUNTS.iter().find(|&&(d, _)| d == degree) {
+        Some(&(_, bounds)) => Ok(bounds[column]),
+        None => Err(Error::InvalidArgument(format!(
+            "poly_modulus_degree {} is not covered by the security standard for {:?}",
+            degree, security_level
+        ))),
+    }
+}
@@ -0,0 +1,286 @@
+use std::io::{Read, Write};
+use std::ptr;
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Shake128, Shake128Reader};
+
+use context::{Context, ContextData};
+use envelope::{Compression, ObjectKind};
+use error::{check, Error, Result};
+use ffi;
+use parameters::{ParmsId, SchemeType};
+use serialization::{self, Serializable};
+
+/// Owned wrapper around SEAL's `Ciphertext`.
+pub struct Ciphertext {
+    ctx: Context,
+    ptr: *mut ffi::seal_Ciphertext,
+}
+
+impl Ciphertext {
+    /// Returns the number of polynomials in the ciphertext. A fresh encryption
+    /// has size 2 and every multiplication grows it.
+    pub fn size(&self) -> Result<usize> {
+        let mut size = 0;
+        check(unsafe { ffi::bindings_Ciphertext_size(self.ptr, &mut size) })?;
+        Ok(size as usize)
+    }
+
+    /// Returns the parms_id of the level of the modulus switching chain the
+    /// ciphertext is at.
+    pub fn parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_Ciphertext_parms_id(self.ptr, words.as_mut_ptr()) })?;
+        Ok(ParmsId::new(words))
+    }
+
+    /// Returns the scale of a CKKS ciphertext. BFV ciphertexts always have
+    /// scale 1.
+    pub fn scale(&self) -> Result<f64> {
+        let mut scale = 0.0;
+        check(unsafe { ffi::bindings_Ciphertext_scale(self.ptr, &mut scale) })?;
+        Ok(scale)
+    }
+
+    /// Overwrites the scale of a CKKS ciphertext without touching its data,
+    /// which reinterprets the encrypted values: they are multiplied by the
+    /// ratio of the old scale to the new one.
+    pub fn set_scale(&mut self, scale: f64) -> Result<()> {
+        check(unsafe { ffi::bindings_Ciphertext_set_scale(self.ptr, scale) })
+    }
+
+    /// Returns whether the polynomials are stored in NTT form. CKKS
+    /// ciphertexts always are; BFV ciphertexts only after
+    /// `Evaluator::transform_to_ntt`.
+    pub fn is_ntt_form(&self) -> Result<bool> {
+        let mut is_ntt_form = false;
+        check(unsafe { ffi::bindings_Ciphertext_is_ntt_form(self.ptr, &mut is_ntt_form) })?;
+        Ok(is_ntt_form)
+    }
+
+    /// Writes the ciphertext to `writer` in SEAL's binary format, wrapped in
+    /// an envelope recording its scheme and parms_id.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Like `save`, but compresses the payload. `load` detects and undoes the
+    /// compression.
+    pub fn save_compressed(&self, writer: &mut impl Write, compression: Compression) -> Result<()> {
+        serialization::save_compressed(self, writer, compression)
+    }
+
+    /// Reads a ciphertext written by `save`, checking that it is valid for
+    /// `ctx`. A ciphertext saved for other parameters is rejected with
+    /// `Error::InvalidArgument` before SEAL reads it.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<Ciphertext> {
+        serialization::load(ctx, reader)
+    }
+
+    /// Reads a ciphertext written by `SeededCiphertext::save` and expands it.
+    pub fn load_seeded(ctx: &Context, reader: &mut impl Read) -> Result<Ciphertext> {
+        SeededCiphertext::load(ctx, reader)?.expand()
+    }
+
+    /// Returns the words of polynomial `index`, one block of
+    /// `poly_modulus_degree` coefficients per prime.
+    pub(crate) fn poly(&self, index: usize) -> Result<Vec<u64>> {
+        let mut count = 0;
+        check(unsafe { ffi::bindings_Ciphertext_poly(self.ptr, index, ptr::null_mut(), &mut count) })?;
+        let mut words = vec![0; count];
+        check(unsafe { ffi::bindings_Ciphertext_poly(self.ptr, index, words.as_mut_ptr(), &mut count) })?;
+        Ok(words)
+    }
+
+    pub(crate) fn from_ptr(ctx: &Context, ptr: *mut ffi::seal_Ciphertext) -> Ciphertext {
+        Ciphertext { ctx: ctx.clone(), ptr }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_Ciphertext {
+        self.ptr
+    }
+}
+
+impl Serializable for Ciphertext {
+    const KIND: ObjectKind = ObjectKind::Ciphertext;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_Ciphertext_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Ciphertext_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(ctx, ptr))
+    }
+}
+
+impl Drop for Ciphertext {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_Ciphertext_Destroy(self.ptr) }
+    }
+}
+
+/// A symmetric encryption whose second polynomial has been replaced by the
+/// 256-bit seed it was expanded from, about half the size of the `Ciphertext`.
+/// Made by `Encryptor::encrypt_seeded` and turned back into a `Ciphertext` by
+/// `expand`.
+pub struct SeededCiphertext {
+    ctx: Context,
+    parms_id: ParmsId,
+    scale: f64,
+    seed: [u64; 4],
+    c0: Vec<u64>,
+}
+
+impl SeededCiphertext {
+    /// Returns the parms_id of the level the ciphertext is at.
+    pub fn parms_id(&self) -> ParmsId {
+        self.parms_id
+    }
+
+    /// Returns the scale of a CKKS ciphertext. BFV ciphertexts always have
+    /// scale 1.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Regenerates the second polynomial from the seed, returning a ciphertext
+    /// identical to the one `Encryptor::encrypt_seeded` compressed.
+    pub fn expand(&self) -> Result<Ciphertext> {
+        let c1 = expand_seed(&self.ctx.context_data(self.parms_id)?, &self.seed)?;
+        if c1.len() != self.c0.len() {
+            return Err(Error::InvalidArgument(format!(
+                "seeded ciphertext has {} coefficients, expected {}",
+                self.c0.len(),
+                c1.len()
+            )));
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Ciphertext_expand_seeded(
+                self.ctx.as_ptr(),
+                self.parms_id.words().as_ptr(),
+                self.c0.as_ptr(),
+                c1.as_ptr(),
+                c1.len(),
+                self.scale,
+                &mut ptr,
+            )
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Writes the ciphertext to `writer`, wrapped in an envelope recording its
+    /// scheme and parms_id.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Reads a ciphertext written by `save`, checking that it is valid for
+    /// `ctx`. `Ciphertext::load_seeded` also expands it.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<SeededCiphertext> {
+        serialization::load(ctx, reader)
+    }
+
+    /// Drops the second polynomial of `encrypted`, which must have been
+    /// expanded from `seed`.
+    pub(crate) fn from_ciphertext(encrypted: &Ciphertext, seed: [u64; 4]) -> Result<SeededCiphertext> {
+        Ok(SeededCiphertext {
+            ctx: encrypted.ctx.clone(),
+            parms_id: encrypted.parms_id()?,
+            scale: encrypted.scale()?,
+            seed,
+            c0: encrypted.poly(0)?,
+        })
+    }
+}
+
+/// Expands `seed` into the second polynomial of a symmetric encryption at the
+/// level `data`, in NTT form.
+pub(crate) fn expand_seed(data: &ContextData, seed: &[u64; 4]) -> Result<Vec<u64>> {
+    let moduli: Vec<u64> = data.coeff_modulus()?.iter().map(|p| p.value()).collect();
+    Ok(uniform_poly(seed, &moduli, data.parms()?.poly_modulus_degree()?))
+}
+
+/// Returns `degree` coefficients uniform modulo each of `moduli`, read as
+/// little-endian words from SHAKE-128 of the little-endian seed. Words at or
+/// above the largest multiple of the prime are rejected, so that the
+/// reduction is unbiased.
+pub(crate) fn uniform_poly(seed: &[u64; 4], moduli: &[u64], degree: usize) -> Vec<u64> {
+    let mut bytes = [0; 32];
+    for (chunk, word) in bytes.chunks_mut(8).zip(seed) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    let mut reader = shake128(&bytes);
+    let mut poly = Vec::with_capacity(moduli.len() * degree);
+    for &modulus in moduli {
+        let bound = u64::MAX / modulus * modulus;
+        let mut count = 0;
+        while count < degree {
+            let mut word = [0; 8];
+            XofReader::read(&mut reader, &mut word);
+            let word = u64::from_le_bytes(word);
+            if word < bound {
+                poly.push(word % modulus);
+                count += 1;
+            }
+        }
+    }
+    poly
+}
+
+/// Returns the SHAKE-128 output stream for `input`.
+pub(crate) fn shake128(input: &[u8]) -> Shake128Reader {
+    let mut hasher = Shake128::default();
+    hasher.update(input);
+    hasher.finalize_xof()
+}
+
+/// The payload is the parms_id, the seed and the first polynomial as
+/// little-endian words, with the scale in between as a little-endian `f64`.
+impl Serializable for SeededCiphertext {
+    const KIND: ObjectKind = ObjectKind::SeededCiphertext;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.parms_id))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(72 + 8 * self.c0.len());
+        for word in self.parms_id.words().iter().chain(&self.seed) {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.scale.to_bits().to_le_bytes());
+        for word in &self.c0 {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        Ok(bytes)
+    }
+
+    /// The first polynomial is checked against `ctx` when it is expanded.
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<SeededCiphertext> {
+        let chunks = bytes.chunks_exact(8);
+        if bytes.len() < 72 || !chunks.remainder().is_empty() {
+            return Err(Error::Io(format!("{} bytes is not a valid seeded ciphertext", bytes.len())));
+        }
+        let mut words = chunks.map(|chunk| {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            u64::from_le_bytes(word)
+        });
+        let mut parms_id = [0; 4];
+        let mut seed = [0; 4];
+        for word in parms_id.iter_mut().chain(seed.iter_mut()) {
+            *word = words.next().unwrap_or(0);
+        }
+        let scale = f64::from_bits(words.next().unwrap_or(0));
+        let parms_id = ParmsId::new(parms_id);
+        ctx.context_data(parms_id)?;
+        Ok(SeededCiphertext { ctx: ctx.clone(), parms_id, scale, seed, c0: words.collect() })
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::ptr;
+use std::sync::Arc;
+use std::vec;
+
+use error::{check, Result};
+use ffi;
+use modulus::SmallModulus;
+use parameters::{EncryptionParameters, ParmsId};
+
+/// The flags SEAL sets while validating a set of encryption parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EncryptionParameterQualifiers {
+    /// Whether SEAL considers the parameters valid at all. Nothing can be
+    /// done with a context whose parameters are not set.
+    pub parameters_set: bool,
+    /// Whether the polynomial modulus degree is a power of two.
+    pub using_fft: bool,
+    /// Whether every coefficient modulus prime is congruent to 1 modulo
+    /// 2 * poly_modulus_degree.
+    pub using_ntt: bool,
+    /// Whether the plain modulus supports `BatchEncoder`.
+    pub using_batching: bool,
+    /// Whether the plain modulus is smaller than every coefficient modulus
+    /// prime, which speeds up `multiply_plain`.
+    pub using_fast_plain_lift: bool,
+    /// Whether the coefficient modulus is within the HomomorphicEncryption.org
+    /// bound for 128-bit security.
+    pub using_he_std_security: bool,
+}
+
+impl From<ffi::seal_EncryptionParameterQualifiers> for EncryptionParameterQualifiers {
+    fn from(raw: ffi::seal_EncryptionParameterQualifiers) -> EncryptionParameterQualifiers {
+        EncryptionParameterQualifiers {
+            parameters_set: raw.parameters_set,
+            using_fft: raw.using_fft,
+            using_ntt: raw.using_ntt,
+            using_batching: raw.using_batching,
+            using_fast_plain_lift: raw.using_fast_plain_lift,
+            using_he_std_security: raw.using_he_std_security,
+        }
+    }
+}
+
+/// Owned wrapper around SEAL's `SEALContext`, which validates a set of
+/// encryption parameters and holds the pre-computations derived from them.
+///
+/// Cloning a `Context` is cheap: every clone shares the same underlying
+/// `SEALContext`, which is freed once the last clone and every object built
+/// from it have been dropped.
+#[derive(Clone)]
+pub struct Context {
+    handle: Arc<ContextHandle>,
+}
+
+struct ContextHandle {
+    ptr: *mut ffi::bindings_ContextHandle,
+}
+
+// A SEALContext is immutable once created, and the handle only owns a
+// `std::shared_ptr` to it, whose reference count is atomic.
+unsafe impl Send for ContextHandle {}
+unsafe impl Sync for ContextHandle {}
+
+impl Drop for ContextHandle {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_SEALContext_Destroy(self.ptr) }
+    }
+}
+
+impl Context {
+    pub fn new(parms: &EncryptionParameters, expand_mod_chain: bool) -> Result<Context> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_SEALContext_Create(parms.as_ptr(), expand_mod_chain, &mut ptr) })?;
+        Ok(Context { handle: Arc::new(ContextHandle { ptr }) })
+    }
+
+    /// Returns whether SEAL considers the parameters of this context valid.
+    pub fn parameters_set(&self) -> Result<bool> {
+        let mut set = false;
+        check(unsafe { ffi::bindings_SEALContext_parameters_set(self.as_ptr(), &mut set) })?;
+        Ok(set)
+    }
+
+    /// Returns the qualifiers of the first set of parameters in the
+    /// modulus switching chain.
+    pub fn qualifiers(&self) -> Result<EncryptionParameterQualifiers> {
+        let mut raw = ffi::seal_EncryptionParameterQualifiers::default();
+        check(unsafe { ffi::bindings_SEALContext_qualifiers(self.as_ptr(), &mut raw) })?;
+        Ok(raw.into())
+    }
+
+    /// Returns the total number of bits in the coefficient modulus of the
+    /// first set of parameters in the modulus switching chain.
+    pub fn total_coeff_modulus_bit_count(&self) -> Result<u32> {
+        let mut count = 0;
+        check(unsafe { ffi::bindings_SEALContext_total_coeff_modulus_bit_count(self.as_ptr(), &mut count) })?;
+        Ok(count as u32)
+    }
+
+    /// Returns the parms_id of the first level of the modulus switching
+    /// chain, which is the one the context was created with.
+    pub fn first_parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_SEALContext_first_parms_id(self.as_ptr(), words.as_mut_ptr()) })?;
+        Ok(ParmsId::new(words))
+    }
+
+    /// Returns the parms_id of the last level of the modulus switching chain.
+    /// It equals `first_parms_id` when the chain was not expanded.
+    pub fn last_parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_SEALContext_last_parms_id(self.as_ptr(), words.as_mut_ptr()) })?;
+        Ok(ParmsId::new(words))
+    }
+
+    /// Returns the level of the modulus switching chain with the given
+    /// parms_id, or `Error::InvalidArgument` if there is none.
+    pub fn context_data(&self, parms_id: ParmsId) -> Result<ContextData> {
+        let data = ContextData { ctx: self.clone(), parms_id };
+        data.chain_index()?;
+        Ok(data)
+    }
+
+    pub fn first_context_data(&self) -> Result<ContextData> {
+        self.context_data(self.first_parms_id()?)
+    }
+
+    pub fn last_context_data(&self) -> Result<ContextData> {
+        self.context_data(self.last_parms_id()?)
+    }
+
+    /// Returns the levels of the modulus switching chain, from the first to
+    /// the last.
+    pub fn chain(&self) -> Result<ContextChain> {
+        let mut levels = Vec::new();
+        let mut next = Some(self.first_context_data()?);
+        while let Some(data) = next {
+            next = data.next()?;
+            levels.push(data);
+        }
+        Ok(ContextChain { levels: levels.into_iter() })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::bindings_ContextHandle {
+        self.handle.ptr
+    }
+}
+
+/// One level of a context's modulus switching chain, identified by its
+/// parms_id.
+///
+/// Each level drops the last prime of the coefficient modulus of the level
+/// before it; modulus switching or rescaling a ciphertext moves it one level
+/// down the chain.
+#[derive(Clone)]
+pub struct ContextData {
+    ctx: Context,
+    parms_id: ParmsId,
+}
+
+impl ContextData {
+    /// Returns the context this level belongs to.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn parms_id(&self) -> ParmsId {
+        self.parms_id
+    }
+
+    /// Returns the position of this level in the chain. The first level has
+    /// index 0, and the index increases by one at every level after it.
+    pub fn chain_index(&self) -> Result<usize> {
+        let mut index = 0;
+        check(unsafe { ffi::bindings_ContextData_chain_index(self.ctx.as_ptr(), self.words().as_ptr(), &mut index) })?;
+        Ok(index)
+    }
+
+    /// Returns a copy of the encryption parameters of this level.
+    pub fn parms(&self) -> Result<EncryptionParameters> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_ContextData_parms(self.ctx.as_ptr(), self.words().as_ptr(), &mut ptr) })?;
+        Ok(EncryptionParameters::from_ptr(ptr))
+    }
+
+    pub fn coeff_modulus(&self) -> Result<Vec<SmallModulus>> {
+        self.parms()?.coeff_modulus()
+    }
+
+    pub fn qualifiers(&self) -> Result<EncryptionParameterQualifiers> {
+        let mut raw = ffi::seal_EncryptionParameterQualifiers::default();
+        check(unsafe { ffi::bindings_ContextData_qualifiers(self.ctx.as_ptr(), self.words().as_ptr(), &mut raw) })?;
+        Ok(raw.into())
+    }
+
+    pub fn total_coeff_modulus_bit_count(&self) -> Result<u32> {
+        let mut count = 0;
+        check(unsafe {
+            ffi::bindings_ContextData_total_coeff_modulus_bit_count(self.ctx.as_ptr(), self.words().as_ptr(), &mut count)
+        })?;
+        Ok(count as u32)
+    }
+
+    /// Returns the level below this one, or `None` if this is the last level.
+    pub fn next(&self) -> Result<Option<ContextData>> {
+        let mut has_next = false;
+        let mut words = [0; 4];
+        check(unsafe {
+            ffi::bindings_ContextData_next_parms_id(
+                self.ctx.as_ptr(),
+                self.words().as_ptr(),
+                &mut has_next,
+                words.as_mut_ptr(),
+            )
+        })?;
+        Ok(if has_next {
+            Some(ContextData { ctx: self.ctx.clone(), parms_id: ParmsId::new(words) })
+        } else {
+            None
+        })
+    }
+
+    /// Returns the level above this one, or `None` if this is the first
+    /// level.
+    ///
+    /// SEAL only links each level to the next one, so this walks the chain
+    /// from the first level.
+    pub fn prev(&self) -> Result<Option<ContextData>> {
+        let mut prev = None;
+        let mut current = self.ctx.first_context_data()?;
+        while current.parms_id != self.parms_id {
+            match current.next()? {
+                Some(next) => {
+                    prev = Some(current);
+                    current = next;
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(prev)
+    }
+
+    fn words(&self) -> [u64; 4] {
+        self.parms_id.words()
+    }
+}
+
+impl fmt::Debug for ContextData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ContextData")
+            .field("chain_index", &self.chain_index().ok())
+            .field("parms_id", &self.parms_id)
+            .finish()
+    }
+}
+
+/// Iterator over the levels of a context's modulus switching chain, returned
+/// by `Context::chain`.
+pub struct ContextChain {
+    levels: vec::IntoIter<ContextData>,
+}
+
+impl Iterator for ContextChain {
+    type Item = ContextData;
+
+    fn next(&mut self) -> Option<ContextData> {
+        self.levels.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.levels.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ContextChain {
+    fn next_back(&mut self) -> Option<ContextData> {
+        self.levels.next_back()
+    }
+}
+
+impl ExactSizeIterator for ContextChain {}
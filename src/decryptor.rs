@@ -0,0 +1,47 @@
+use std::ptr;
+
+use ciphertext::Ciphertext;
+use context::Context;
+use error::{check, Result};
+use ffi;
+use keys::SecretKey;
+use plaintext::Plaintext;
+
+/// Decrypts ciphertexts with a secret key.
+pub struct Decryptor {
+    ctx: Context,
+    ptr: *mut ffi::seal_Decryptor,
+}
+
+impl Decryptor {
+    pub fn new(ctx: &Context, secret_key: &SecretKey) -> Result<Decryptor> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Decryptor_Create(ctx.as_ptr(), secret_key.as_ptr(), &mut ptr) })?;
+        Ok(Decryptor { ctx: ctx.clone(), ptr })
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn decrypt(&self, encrypted: &Ciphertext) -> Result<Plaintext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Decryptor_decrypt(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Returns the invariant noise budget of a ciphertext, in bits. Once it
+    /// reaches zero the ciphertext can no longer be decrypted correctly.
+    pub fn invariant_noise_budget(&self, encrypted: &Ciphertext) -> Result<i32> {
+        let mut budget = 0;
+        check(unsafe { ffi::bindings_Decryptor_invariant_noise_budget(self.ptr, encrypted.as_ptr(), &mut budget) })?;
+        Ok(budget)
+    }
+}
+
+impl Drop for Decryptor {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_Decryptor_Destroy(self.ptr) }
+    }
+}
@@ -0,0 +1,444 @@
+use std::ptr;
+
+use num_complex::Complex;
+
+use biguint::BigUInt;
+use context::Context;
+use error::{check, Error, Result};
+use ffi;
+use parameters::ParmsId;
+use plaintext::Plaintext;
+use slot_matrix::SlotMatrix;
+
+/// Encodes integers as plaintext polynomials by their expansion in a base,
+/// binary by default.
+///
+/// SEAL 3.1 only implements base 2. Other bases are handled by this crate:
+/// each digit is taken in (-base/2, base/2] and stored as a coefficient
+/// modulo the plain modulus, and decoding evaluates the polynomial at the
+/// base, reading coefficients above half the plain modulus as negative.
+pub struct IntegerEncoder {
+    ctx: Context,
+    ptr: *mut ffi::seal_IntegerEncoder,
+    base: u64,
+    plain_modulus: u64,
+}
+
+impl IntegerEncoder {
+    pub fn new(ctx: &Context) -> Result<IntegerEncoder> {
+        IntegerEncoder::with_base(ctx, 2)
+    }
+
+    /// Creates an encoder using the given base, which must be at least 2 and
+    /// smaller than the plain modulus.
+    pub fn with_base(ctx: &Context, base: u64) -> Result<IntegerEncoder> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_IntegerEncoder_Create(ctx.as_ptr(), &mut ptr) })?;
+        let mut encoder = IntegerEncoder { ctx: ctx.clone(), ptr, base, plain_modulus: 0 };
+        encoder.plain_modulus = ctx.first_context_data()?.parms()?.plain_modulus()?.value();
+        if base < 2 || base >= encoder.plain_modulus {
+            return Err(Error::InvalidArgument(format!(
+                "base {} is not between 2 and the plain modulus {}",
+                base, encoder.plain_modulus
+            )));
+        }
+        Ok(encoder)
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    pub fn encode(&self, value: i64) -> Result<Plaintext> {
+        if self.base != 2 {
+            return self.encode_digits(BigUInt::from(value.wrapping_abs() as u64), value < 0);
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_IntegerEncoder_encode_int64(self.ptr, value, &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn encode_u64(&self, value: u64) -> Result<Plaintext> {
+        if self.base != 2 {
+            return self.encode_digits(BigUInt::from(value), false);
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_IntegerEncoder_encode_uint64(self.ptr, value, &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn encode_biguint(&self, value: &BigUInt) -> Result<Plaintext> {
+        if self.base != 2 {
+            return self.encode_digits(value.clone(), false);
+        }
+        let words = value.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_IntegerEncoder_encode_biguint(self.ptr, words.as_ptr(), words.len(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Decodes a plaintext, returning `Error::InvalidArgument` if the value
+    /// does not fit in an `i32`.
+    pub fn decode_i32(&self, plain: &Plaintext) -> Result<i32> {
+        if self.base != 2 {
+            let value = self.decode_i64(plain)?;
+            if value < i64::from(i32::MIN) || value > i64::from(i32::MAX) {
+                return Err(out_of_range("i32"));
+            }
+            return Ok(value as i32);
+        }
+        let mut value = 0;
+        check(unsafe { ffi::bindings_IntegerEncoder_decode_int32(self.ptr, plain.as_ptr(), &mut value) })?;
+        Ok(value)
+    }
+
+    /// Decodes a plaintext, returning `Error::InvalidArgument` if the value
+    /// does not fit in an `i64`.
+    pub fn decode_i64(&self, plain: &Plaintext) -> Result<i64> {
+        if self.base != 2 {
+            let (positive, negative) = self.decode_digits(plain)?;
+            return match positive.checked_sub(&negative) {
+                Some(value) => match value.to_u64() {
+                    Some(value) if value <= i64::MAX as u64 => Ok(value as i64),
+                    _ => Err(out_of_range("i64")),
+                },
+                None => match negative.checked_sub(&positive).and_then(|value| value.to_u64()) {
+                    Some(value) if value <= 1 << 63 => Ok((value as i64).wrapping_neg()),
+                    _ => Err(out_of_range("i64")),
+                },
+            };
+        }
+        let mut value = 0;
+        check(unsafe { ffi::bindings_IntegerEncoder_decode_int64(self.ptr, plain.as_ptr(), &mut value) })?;
+        Ok(value)
+    }
+
+    /// Decodes a plaintext, returning `Error::InvalidArgument` if the value
+    /// is negative or does not fit in a `u64`.
+    pub fn decode_u64(&self, plain: &Plaintext) -> Result<u64> {
+        if self.base != 2 {
+            return self.decode_biguint(plain)?.to_u64().ok_or_else(|| out_of_range("u64"));
+        }
+        let mut value = 0;
+        check(unsafe { ffi::bindings_IntegerEncoder_decode_uint64(self.ptr, plain.as_ptr(), &mut value) })?;
+        Ok(value)
+    }
+
+    /// Decodes a plaintext, returning `Error::InvalidArgument` if the value
+    /// is negative.
+    pub fn decode_biguint(&self, plain: &Plaintext) -> Result<BigUInt> {
+        if self.base != 2 {
+            let (positive, negative) = self.decode_digits(plain)?;
+            return positive.checked_sub(&negative).ok_or_else(|| out_of_range("BigUInt"));
+        }
+        let mut count = 0;
+        check(unsafe {
+            ffi::bindings_IntegerEncoder_decode_biguint(self.ptr, plain.as_ptr(), ptr::null_mut(), &mut count)
+        })?;
+        let mut words = vec![0; count];
+        check(unsafe {
+            ffi::bindings_IntegerEncoder_decode_biguint(self.ptr, plain.as_ptr(), words.as_mut_ptr(), &mut count)
+        })?;
+        Ok(BigUInt::from_words(words))
+    }
+
+    fn encode_digits(&self, mut magnitude: BigUInt, negative: bool) -> Result<Plaintext> {
+        let mut coeffs = Vec::new();
+        while !magnitude.is_zero() {
+            let mut digit = magnitude.divrem_small(self.base);
+            let mut digit_negative = negative;
+            if digit > self.base / 2 {
+                digit = self.base - digit;
+                digit_negative = !negative;
+                magnitude.mul_add_small(1, 1);
+            }
+            coeffs.push(if digit_negative && digit != 0 { self.plain_modulus - digit } else { digit });
+        }
+        Plaintext::from_coeffs(&coeffs)
+    }
+
+    /// Evaluates the polynomial at the base, returning the sums of its
+    /// positive and of its negative terms.
+    fn decode_digits(&self, plain: &Plaintext) -> Result<(BigUInt, BigUInt)> {
+        let threshold = self.plain_modulus - self.plain_modulus / 2;
+        let mut positive = BigUInt::default();
+        let mut negative = BigUInt::default();
+        for &coeff in plain.coeffs()?.iter().rev() {
+            if coeff >= self.plain_modulus {
+                return Err(Error::InvalidArgument("plain is not valid for the plain modulus".to_owned()));
+            }
+            let (pos, neg) = if coeff < threshold { (coeff, 0) } else { (0, self.plain_modulus - coeff) };
+            positive.mul_add_small(self.base, pos);
+            negative.mul_add_small(self.base, neg);
+        }
+        Ok((positive, negative))
+    }
+}
+
+fn out_of_range(target: &str) -> Error {
+    Error::InvalidArgument(format!("the decoded value does not fit in {}", target))
+}
+
+impl Drop for IntegerEncoder {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_IntegerEncoder_Destroy(self.ptr) }
+    }
+}
+
+/// Encodes vectors of integers into the slots of a plaintext when the plain
+/// modulus supports batching.
+///
+/// A plaintext has `slot_count()` slots, each holding an integer modulo the
+/// plain modulus, and homomorphic operations act on every slot at once.
+pub struct BatchEncoder {
+    ctx: Context,
+    ptr: *mut ffi::seal_BatchEncoder,
+    slot_count: usize,
+    plain_modulus: u64,
+}
+
+impl BatchEncoder {
+    /// Creates a batch encoder, returning `Error::InvalidArgument` if the plain
+    /// modulus is not a prime congruent to 1 modulo 2 * poly_modulus_degree.
+    pub fn new(ctx: &Context) -> Result<BatchEncoder> {
+        if !ctx.qualifiers()?.using_batching {
+            return Err(Error::InvalidArgument(
+                "the plain modulus does not support batching".to_owned(),
+            ));
+        }
+        let plain_modulus = ctx.first_context_data()?.parms()?.plain_modulus()?.value();
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_BatchEncoder_Create(ctx.as_ptr(), &mut ptr) })?;
+        let mut encoder = BatchEncoder { ctx: ctx.clone(), ptr, slot_count: 0, plain_modulus };
+        check(unsafe { ffi::bindings_BatchEncoder_slot_count(ptr, &mut encoder.slot_count) })?;
+        Ok(encoder)
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the number of slots, which equals the polynomial modulus
+    /// degree.
+    pub fn slot_count(&self) -> usize {
+        self.slot_count
+    }
+
+    /// Encodes up to `slot_count()` values, each smaller than the plain
+    /// modulus. Slots past the end of `values` are set to zero.
+    pub fn encode(&self, values: &[u64]) -> Result<Plaintext> {
+        self.check_len(values.len())?;
+        if let Some(value) = values.iter().find(|&&value| value >= self.plain_modulus) {
+            return Err(Error::InvalidArgument(format!(
+                "{} is not smaller than the plain modulus {}",
+                value, self.plain_modulus
+            )));
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_BatchEncoder_encode_uint64(self.ptr, values.as_ptr(), values.len(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Encodes up to `slot_count()` signed values, each at most half the plain
+    /// modulus in absolute value. Negative values are stored as their
+    /// representative modulo the plain modulus.
+    pub fn encode_signed(&self, values: &[i64]) -> Result<Plaintext> {
+        self.check_len(values.len())?;
+        let bound = (self.plain_modulus / 2) as i64;
+        if let Some(value) = values.iter().find(|&&value| value < -bound || value > bound) {
+            return Err(Error::InvalidArgument(format!(
+                "{} is out of range for the plain modulus {}",
+                value, self.plain_modulus
+            )));
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_BatchEncoder_encode_int64(self.ptr, values.as_ptr(), values.len(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Returns the `slot_count()` values stored in a plaintext.
+    pub fn decode(&self, plain: &Plaintext) -> Result<Vec<u64>> {
+        let mut values = vec![0; self.slot_count];
+        check(unsafe { ffi::bindings_BatchEncoder_decode_uint64(self.ptr, plain.as_ptr(), values.as_mut_ptr()) })?;
+        Ok(values)
+    }
+
+    /// Returns the `slot_count()` values stored in a plaintext, mapping those
+    /// above half the plain modulus to negative numbers.
+    pub fn decode_signed(&self, plain: &Plaintext) -> Result<Vec<i64>> {
+        let mut values = vec![0; self.slot_count];
+        check(unsafe { ffi::bindings_BatchEncoder_decode_int64(self.ptr, plain.as_ptr(), values.as_mut_ptr()) })?;
+        Ok(values)
+    }
+
+    /// Encodes a matrix with two rows of `slot_count() / 2` values.
+    pub fn encode_matrix(&self, matrix: &SlotMatrix<u64>) -> Result<Plaintext> {
+        self.check_row_size(matrix.row_size())?;
+        self.encode(matrix.as_slice())
+    }
+
+    /// Encodes a matrix with two rows of `slot_count() / 2` signed values.
+    pub fn encode_signed_matrix(&self, matrix: &SlotMatrix<i64>) -> Result<Plaintext> {
+        self.check_row_size(matrix.row_size())?;
+        self.encode_signed(matrix.as_slice())
+    }
+
+    /// Returns the slots of a plaintext as a matrix.
+    pub fn decode_matrix(&self, plain: &Plaintext) -> Result<SlotMatrix<u64>> {
+        SlotMatrix::from_vec(self.decode(plain)?)
+    }
+
+    /// Returns the slots of a plaintext as a matrix of signed values.
+    pub fn decode_signed_matrix(&self, plain: &Plaintext) -> Result<SlotMatrix<i64>> {
+        SlotMatrix::from_vec(self.decode_signed(plain)?)
+    }
+
+    fn check_row_size(&self, row_size: usize) -> Result<()> {
+        if 2 * row_size != self.slot_count {
+            return Err(Error::InvalidArgument(format!(
+                "matrix rows have {} values, expected {}",
+                row_size,
+                self.slot_count / 2
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_len(&self, len: usize) -> Result<()> {
+        if len > self.slot_count {
+            return Err(Error::InvalidArgument(format!(
+                "{} values do not fit in {} slots",
+                len, self.slot_count
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for BatchEncoder {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_BatchEncoder_Destroy(self.ptr) }
+    }
+}
+
+/// Encodes vectors of real or complex numbers for the CKKS scheme.
+///
+/// A plaintext holds `slot_count()` complex numbers, which is half the
+/// polynomial modulus degree. Each value is multiplied by `scale` and rounded
+/// to an integer, so the scale sets the precision of the encoding; it must
+/// stay well below the coefficient modulus of the level encoded for.
+pub struct CkksEncoder {
+    ctx: Context,
+    ptr: *mut ffi::seal_CKKSEncoder,
+    slot_count: usize,
+}
+
+impl CkksEncoder {
+    /// Creates a CKKS encoder, returning `Error::InvalidArgument` if the
+    /// context does not use the CKKS scheme.
+    pub fn new(ctx: &Context) -> Result<CkksEncoder> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_CKKSEncoder_Create(ctx.as_ptr(), &mut ptr) })?;
+        let mut encoder = CkksEncoder { ctx: ctx.clone(), ptr, slot_count: 0 };
+        check(unsafe { ffi::bindings_CKKSEncoder_slot_count(ptr, &mut encoder.slot_count) })?;
+        Ok(encoder)
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the number of slots, which is half the polynomial modulus
+    /// degree.
+    pub fn slot_count(&self) -> usize {
+        self.slot_count
+    }
+
+    /// Encodes up to `slot_count()` real numbers for the level of the
+    /// modulus switching chain with the given parms_id. Slots past the end
+    /// of `values` are set to zero.
+    pub fn encode_f64(&self, values: &[f64], scale: f64, parms_id: ParmsId) -> Result<Plaintext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_CKKSEncoder_encode_double(
+                self.ptr,
+                values.as_ptr(),
+                values.len(),
+                words.as_ptr(),
+                scale,
+                &mut ptr,
+            )
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Encodes up to `slot_count()` complex numbers, like `encode_f64`.
+    pub fn encode_complex(&self, values: &[Complex<f64>], scale: f64, parms_id: ParmsId) -> Result<Plaintext> {
+        let mut flat = Vec::with_capacity(2 * values.len());
+        for c in values {
+            flat.push(c.re);
+            flat.push(c.im);
+        }
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_CKKSEncoder_encode_complex(
+                self.ptr,
+                flat.as_ptr(),
+                values.len(),
+                words.as_ptr(),
+                scale,
+                &mut ptr,
+            )
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Encodes a real number into every slot.
+    pub fn encode_scalar(&self, value: f64, scale: f64, parms_id: ParmsId) -> Result<Plaintext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_CKKSEncoder_encode_double_scalar(self.ptr, value, words.as_ptr(), scale, &mut ptr)
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Encodes a complex number into every slot.
+    pub fn encode_complex_scalar(&self, value: Complex<f64>, scale: f64, parms_id: ParmsId) -> Result<Plaintext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_CKKSEncoder_encode_complex_scalar(self.ptr, value.re, value.im, words.as_ptr(), scale, &mut ptr)
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Returns the real parts of the `slot_count()` values in a plaintext.
+    pub fn decode(&self, plain: &Plaintext) -> Result<Vec<f64>> {
+        let mut values = vec![0.0; self.slot_count];
+        check(unsafe { ffi::bindings_CKKSEncoder_decode_double(self.ptr, plain.as_ptr(), values.as_mut_ptr()) })?;
+        Ok(values)
+    }
+
+    /// Returns the `slot_count()` complex values in a plaintext.
+    pub fn decode_complex(&self, plain: &Plaintext) -> Result<Vec<Complex<f64>>> {
+        let mut flat = vec![0.0; 2 * self.slot_count];
+        check(unsafe { ffi::bindings_CKKSEncoder_decode_complex(self.ptr, plain.as_ptr(), flat.as_mut_ptr()) })?;
+        Ok(flat.chunks(2).map(|c| Complex::new(c[0], c[1])).collect())
+    }
+}
+
+impl Drop for CkksEncoder {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_CKKSEncoder_Destroy(self.ptr) }
+    }
+}
@@ -0,0 +1,99 @@
+use std::ptr;
+
+use ciphertext::{self, Ciphertext, SeededCiphertext};
+use context::Context;
+use error::{check, Error, Result};
+use ffi;
+use keys::{PublicKey, SecretKey};
+use parameters::SchemeType;
+use plaintext::Plaintext;
+use serialization;
+
+/// Encrypts plaintexts with a public key or, in symmetric mode, with the
+/// secret key.
+pub struct Encryptor {
+    ctx: Context,
+    mode: Mode,
+}
+
+enum Mode {
+    PublicKey(*mut ffi::seal_Encryptor),
+    SecretKey(*mut ffi::bindings_SymmetricEncryptor),
+}
+
+impl Encryptor {
+    pub fn new(ctx: &Context, public_key: &PublicKey) -> Result<Encryptor> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Encryptor_Create(ctx.as_ptr(), public_key.as_ptr(), &mut ptr) })?;
+        Ok(Encryptor { ctx: ctx.clone(), mode: Mode::PublicKey(ptr) })
+    }
+
+    /// Creates an encryptor in symmetric mode, which encrypts with a copy of
+    /// the secret key and can compress its ciphertexts with `encrypt_seeded`.
+    pub fn with_secret_key(ctx: &Context, secret_key: &SecretKey) -> Result<Encryptor> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_SymmetricEncryptor_Create(ctx.as_ptr(), secret_key.as_ptr(), &mut ptr) })?;
+        Ok(Encryptor { ctx: ctx.clone(), mode: Mode::SecretKey(ptr) })
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn encrypt(&self, plain: &Plaintext) -> Result<Ciphertext> {
+        match self.mode {
+            Mode::PublicKey(enc) => {
+                let mut ptr = ptr::null_mut();
+                check(unsafe { ffi::bindings_Encryptor_encrypt(enc, plain.as_ptr(), &mut ptr) })?;
+                Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+            }
+            Mode::SecretKey(_) => Ok(self.encrypt_symmetric(plain)?.0),
+        }
+    }
+
+    /// Encrypts `plain` in symmetric mode and replaces the random half of the
+    /// result with the seed it was expanded from, for sending to a server
+    /// that restores it with `SeededCiphertext::expand`. Returns
+    /// `Error::LogicError` for an encryptor created with a public key.
+    pub fn encrypt_seeded(&self, plain: &Plaintext) -> Result<SeededCiphertext> {
+        let (encrypted, seed) = self.encrypt_symmetric(plain)?;
+        SeededCiphertext::from_ciphertext(&encrypted, seed)
+    }
+
+    /// Encrypts `plain` in symmetric mode, returning the ciphertext and the
+    /// seed its second polynomial was expanded from.
+    pub(crate) fn encrypt_symmetric(&self, plain: &Plaintext) -> Result<(Ciphertext, [u64; 4])> {
+        let enc = match self.mode {
+            Mode::SecretKey(enc) => enc,
+            Mode::PublicKey(_) => {
+                return Err(Error::LogicError(
+                    "seeded encryption needs an encryptor created with_secret_key".to_owned(),
+                ))
+            }
+        };
+        // BFV encrypts at the first level and CKKS at the level of the
+        // plaintext, as public key encryption does.
+        let parms_id = match serialization::context_scheme(&self.ctx)? {
+            SchemeType::Bfv => self.ctx.first_parms_id()?,
+            SchemeType::Ckks => plain.parms_id()?,
+        };
+        let mut seed = [0; 4];
+        check(unsafe { ffi::bindings_SymmetricEncryptor_draw_seed(enc, seed.as_mut_ptr()) })?;
+        let c1 = ciphertext::expand_seed(&self.ctx.context_data(parms_id)?, &seed)?;
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_SymmetricEncryptor_encrypt(enc, plain.as_ptr(), c1.as_ptr(), c1.len(), &mut ptr)
+        })?;
+        Ok((Ciphertext::from_ptr(&self.ctx, ptr), seed))
+    }
+}
+
+impl Drop for Encryptor {
+    fn drop(&mut self) {
+        match self.mode {
+            Mode::PublicKey(ptr) => unsafe { ffi::bindings_Encryptor_Destroy(ptr) },
+            Mode::SecretKey(ptr) => unsafe { ffi::bindings_SymmetricEncryptor_Destroy(ptr) },
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! The framed format every `save` in this crate writes and every `load` reads.
+//!
+//! SEAL's own streams carry no magic number, version or parameter
+//! fingerprint, so an object read into the wrong context either throws deep
+//! inside SEAL or decodes into garbage. The envelope header records what the
+//! payload is and which parameters it belongs to, so that `load` can reject a
+//! mismatch before SEAL sees the payload.
+//!
+//! An envelope is laid out as follows, with integers in little-endian byte
+//! order:
+//!
+//! | Offset | Size | Field                                              |
+//! |--------|------|----------------------------------------------------|
+//! | 0      | 4    | `MAGIC`                                            |
+//! | 4      | 2    | format version                                     |
+//! | 6      | 1    | object kind                                        |
+//! | 7      | 1    | scheme                                             |
+//! | 8      | 32   | parms_id, as four 64-bit words                     |
+//! | 40     | 1    | compression                                        |
+//! | 41     | 4    | CRC-32 of the header and the payload as stored     |
+//! | 45     | 8    | payload length                                     |
+//! | 53     |      | payload: SEAL's binary format, possibly compressed |
+//!
+//! The CRC-32 is computed over the header, with the checksum field zeroed,
+//! followed by the payload.
+//!
+//! Envelopes can follow each other in one stream.
+
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+#[cfg(feature = "deflate")]
+use flate2;
+#[cfg(feature = "zstd")]
+use zstd;
+
+use context::Context;
+use error::{Error, Result};
+use parameters::{ParmsId, SchemeType};
+use serialization::{context_scheme, io_error};
+
+/// The first four bytes of every envelope.
+pub const MAGIC: [u8; 4] = *b"SLRS";
+
+/// The format version written by this crate. Envelopes with a newer version
+/// are rejected.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The size of the header in bytes.
+pub const HEADER_SIZE: usize = 53;
+
+/// SEAL's `SEAL_CIPHERTEXT_SIZE_MAX`.
+const MAX_CIPHERTEXT_SIZE: u64 = 16;
+
+/// SEAL's `SEAL_DBC_MAX`.
+const MAX_DECOMPOSITION_BIT_COUNT: i32 = 60;
+
+/// A bound on the bytes a SEAL stream or seeded ciphertext payload has besides
+/// its coefficients.
+const STREAM_OVERHEAD: u64 = 1024;
+
+/// A bound on the size of the SEAL stream of encryption parameters.
+pub(crate) const MAX_PARAMETERS_SIZE: u64 = 1 << 16;
+
+/// The bytes of a decompressed payload read before its limit is computed:
+/// the start of a relinearization or Galois key stream, which holds the
+/// parms_id, the decomposition bit count as an `i32` and the number of keys as
+/// a `u64`.
+const LIMIT_PREFIX_SIZE: usize = 44;
+
+/// The type of object stored in an envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    EncryptionParameters = 1,
+    Ciphertext = 2,
+    Plaintext = 3,
+    PublicKey = 4,
+    SecretKey = 5,
+    RelinKeys = 6,
+    GaloisKeys = 7,
+    SeededCiphertext = 8,
+}
+
+impl ObjectKind {
+    fn from_raw(value: u8) -> Result<ObjectKind> {
+        match value {
+            1 => Ok(ObjectKind::EncryptionParameters),
+            2 => Ok(ObjectKind::Ciphertext),
+            3 => Ok(ObjectKind::Plaintext),
+            4 => Ok(ObjectKind::PublicKey),
+            5 => Ok(ObjectKind::SecretKey),
+            6 => Ok(ObjectKind::RelinKeys),
+            7 => Ok(ObjectKind::GaloisKeys),
+            8 => Ok(ObjectKind::SeededCiphertext),
+            _ => Err(Error::Io(format!("unknown object kind {} in envelope", value))),
+        }
+    }
+}
+
+/// How the payload of an envelope is compressed.
+///
+/// Every variant can be named, but compressing or decompressing needs the
+/// cargo feature of the same name (`zstd` or `deflate`); without it both fail
+/// with `Error::InvalidArgument`.
+///
+/// Ciphertext and key coefficients are reduced modulo primes smaller than
+/// 64 bits, so their top bits are always zero and compress well even though
+/// the rest of each word looks random. The savings are largest for
+/// parameters with small primes, such as the default BFV coefficient moduli.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    None = 0,
+    /// Zstandard at its default level.
+    Zstd = 1,
+    /// Raw DEFLATE at its default level.
+    Deflate = 2,
+}
+
+impl Compression {
+    fn from_raw(value: u8) -> Result<Compression> {
+        match value {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Zstd),
+            2 => Ok(Compression::Deflate),
+            _ => Err(Error::Io(format!("unknown compression {} in envelope", value))),
+        }
+    }
+
+    /// Returns whether this build of the crate supports the compression.
+    pub fn is_available(self) -> bool {
+        match self {
+            Compression::None => true,
+            Compression::Zstd => cfg!(feature = "zstd"),
+            Compression::Deflate => cfg!(feature = "deflate"),
+        }
+    }
+
+    fn compress(self, payload: &[u8]) -> Result<Cow<'_, [u8]>> {
+        match self {
+            Compression::None => Ok(Cow::Borrowed(payload)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Cow::Owned(zstd::stream::encode_all(payload, 0).map_err(io_error)?)),
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => {
+                let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(payload).map_err(io_error)?;
+                Ok(Cow::Owned(encoder.finish().map_err(io_error)?))
+            }
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => Err(unavailable("zstd")),
+            #[cfg(not(feature = "deflate"))]
+            Compression::Deflate => Err(unavailable("deflate")),
+        }
+    }
+
+    /// Decompresses `payload`, failing with `Error::Io` rather than producing
+    /// more bytes than `limit` returns for the start of the output.
+    #[cfg_attr(not(any(feature = "zstd", feature = "deflate")), allow(unused_variables))]
+    fn decompress(self, payload: Vec<u8>, limit: &dyn Fn(&[u8]) -> Result<u64>) -> Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(payload),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => read_limited(zstd::stream::read::Decoder::new(&payload[..]).map_err(io_error)?, limit),
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => read_limited(flate2::read::DeflateDecoder::new(&payload[..]), limit),
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => Err(unavailable("zstd")),
+            #[cfg(not(feature = "deflate"))]
+            Compression::Deflate => Err(unavailable("deflate")),
+        }
+    }
+}
+
+#[cfg(any(feature = "zstd", feature = "deflate"))]
+fn read_limited<R: Read>(mut decoder: R, limit: &dyn Fn(&[u8]) -> Result<u64>) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    decoder.by_ref().take(LIMIT_PREFIX_SIZE as u64).read_to_end(&mut decompressed).map_err(io_error)?;
+    let limit = limit(&decompressed)?;
+    let remaining = limit.saturating_add(1).saturating_sub(decompressed.len() as u64);
+    decoder.take(remaining).read_to_end(&mut decompressed).map_err(io_error)?;
+    if decompressed.len() as u64 > limit {
+        return Err(Error::Io(format!("decompressed payload exceeds the limit of {} bytes", limit)));
+    }
+    Ok(decompressed)
+}
+
+#[cfg(not(all(feature = "zstd", feature = "deflate")))]
+fn unavailable(feature: &str) -> Error {
+    Error::InvalidArgument(format!("this compression needs seal-rs to be built with the `{}` feature", feature))
+}
+
+/// The header of an envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub version: u16,
+    pub kind: ObjectKind,
+    pub scheme: SchemeType,
+    /// The parms_id of the level the object is at. It is zero for plaintexts
+    /// not in NTT form, which are not tied to a level.
+    pub parms_id: ParmsId,
+    pub compression: Compression,
+    /// The CRC-32 of the header with this field zeroed and of the payload as
+    /// stored, i.e. after compression.
+    pub checksum: u32,
+    pub payload_len: u64,
+}
+
+impl Header {
+    /// Reads a header from `reader`, leaving it at the start of the payload.
+    /// Fails with `Error::Io` if the magic number is wrong, the format version
+    /// is newer than `FORMAT_VERSION` or a field has an unknown value.
+    pub fn read<R: Read + ?Sized>(reader: &mut R) -> Result<Header> {
+        let mut bytes = [0; HEADER_SIZE];
+        reader.read_exact(&mut bytes).map_err(io_error)?;
+        if bytes[0..4] != MAGIC {
+            return Err(Error::Io("not a seal-rs envelope: bad magic number".to_owned()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version == 0 || version > FORMAT_VERSION {
+            return Err(Error::Io(format!(
+                "unsupported envelope format version {} (this crate reads up to {})",
+                version, FORMAT_VERSION
+            )));
+        }
+        let mut words = [0; 4];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = u64::from_le_bytes(array8(&bytes[8 + 8 * i..16 + 8 * i]));
+        }
+        Ok(Header {
+            version,
+            kind: ObjectKind::from_raw(bytes[6])?,
+            scheme: SchemeType::from_raw(i32::from(bytes[7])).map_err(|e| Error::Io(e.message().to_owned()))?,
+            parms_id: ParmsId::new(words),
+            compression: Compression::from_raw(bytes[40])?,
+            checksum: u32::from_le_bytes([bytes[41], bytes[42], bytes[43], bytes[44]]),
+            payload_len: u64::from_le_bytes(array8(&bytes[45..53])),
+        })
+    }
+
+    fn write<W: Write + ?Sized>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes()).map_err(io_error)
+    }
+
+    /// Returns the checksum of this header and `payload`.
+    fn compute_checksum(&self, payload: &[u8]) -> u32 {
+        let header = Header { checksum: 0, ..*self };
+        crc32(&[&header.to_bytes(), payload])
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.push(self.kind as u8);
+        bytes.push(self.scheme as u8);
+        for word in &self.parms_id.words() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.push(self.compression as u8);
+        bytes.extend_from_slice(&self.checksum.to_le_bytes());
+        bytes.extend_from_slice(&self.payload_len.to_le_bytes());
+        bytes
+    }
+
+    /// Returns `Error::InvalidArgument` unless the envelope holds an object of
+    /// the given kind.
+    pub fn check_kind(&self, kind: ObjectKind) -> Result<()> {
+        if self.kind != kind {
+            return Err(Error::InvalidArgument(format!("expected {:?} but envelope holds {:?}", kind, self.kind)));
+        }
+        Ok(())
+    }
+
+    /// Returns `Error::InvalidArgument` unless the object in the envelope was
+    /// created for the same scheme as `ctx` and its parms_id is in the modulus
+    /// switching chain of `ctx`.
+    pub fn check_context(&self, ctx: &Context) -> Result<()> {
+        let scheme = context_scheme(ctx)?;
+        if self.scheme != scheme {
+            return Err(Error::InvalidArgument(format!(
+                "envelope holds a {:?} {:?} but the context is for {:?}",
+                self.scheme, self.kind, scheme
+            )));
+        }
+        if self.parms_id != ParmsId::default() && ctx.context_data(self.parms_id).is_err() {
+            return Err(Error::InvalidArgument(format!(
+                "envelope holds a {:?} for parms_id {}, which is not in the context's modulus switching chain",
+                self.kind, self.parms_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Writes `payload` to `writer` in an envelope with the given header fields,
+/// compressing it first.
+pub(crate) fn write<W: Write + ?Sized>(
+    writer: &mut W,
+    kind: ObjectKind,
+    scheme: SchemeType,
+    parms_id: ParmsId,
+    compression: Compression,
+    payload: &[u8],
+) -> Result<()> {
+    let payload = compression.compress(payload)?;
+    let mut header = Header {
+        version: FORMAT_VERSION,
+        kind,
+        scheme,
+        parms_id,
+        compression,
+        checksum: 0,
+        payload_len: payload.len() as u64,
+    };
+    header.checksum = header.compute_checksum(&payload);
+    header.write(writer)?;
+    writer.write_all(&payload).map_err(io_error)
+}
+
+/// Returns the largest decompressed payload accepted for an object of the
+/// given kind in `ctx`, whose decompressed payload starts with `prefix`.
+///
+/// Relinearization and Galois keys are bounded by the number of keys and the
+/// decomposition bit count at the start of their stream, each checked against
+/// the largest value SEAL allows for `ctx`. Everything else is bounded by the
+/// size of the largest ciphertext of `ctx`.
+pub(crate) fn decompressed_limit(ctx: &Context, kind: ObjectKind, prefix: &[u8]) -> Result<u64> {
+    let parms = ctx.first_context_data()?.parms()?;
+    let coeff_modulus = parms.coeff_modulus()?;
+    let degree = parms.poly_modulus_degree()? as u64;
+    let poly_size = degree * coeff_modulus.len() as u64 * 8;
+    let max_keys = match kind {
+        ObjectKind::RelinKeys => MAX_CIPHERTEXT_SIZE - 2,
+        ObjectKind::GaloisKeys => degree,
+        _ => return Ok(MAX_CIPHERTEXT_SIZE * poly_size + STREAM_OVERHEAD),
+    };
+    if prefix.len() < LIMIT_PREFIX_SIZE {
+        return Err(Error::Io(format!("{:?} stream ends after {} bytes", kind, prefix.len())));
+    }
+    let decomposition_bit_count = i32::from_le_bytes([prefix[32], prefix[33], prefix[34], prefix[35]]);
+    let keys = u64::from_le_bytes(array8(&prefix[36..44]));
+    if !(1..=MAX_DECOMPOSITION_BIT_COUNT).contains(&decomposition_bit_count) || keys > max_keys {
+        return Err(Error::Io(format!(
+            "{:?} stream has {} keys with decomposition bit count {}, which the context does not allow",
+            kind, keys, decomposition_bit_count
+        )));
+    }
+    // Each key holds one ciphertext of size 2 per decomposition of each prime.
+    let decomposition_bit_count = decomposition_bit_count as u32;
+    let decomposition_count: u64 = coeff_modulus
+        .iter()
+        .map(|prime| u64::from((prime.bit_count() - 1) / decomposition_bit_count + 1))
+        .sum();
+    Ok(keys * (8 + decomposition_count * (2 * poly_size + STREAM_OVERHEAD)) + STREAM_OVERHEAD)
+}
+
+/// Reads the payload following `header` from `reader`, checks it and the
+/// header against the checksum and decompresses it to at most as many bytes as
+/// `limit` returns for the start of the decompressed payload.
+pub(crate) fn read_payload<R: Read + ?Sized>(
+    reader: &mut R,
+    header: &Header,
+    limit: &dyn Fn(&[u8]) -> Result<u64>,
+) -> Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    reader.take(header.payload_len).read_to_end(&mut payload).map_err(io_error)?;
+    if (payload.len() as u64) < header.payload_len {
+        return Err(Error::Io(format!(
+            "envelope ended after {} of {} payload bytes",
+            payload.len(),
+            header.payload_len
+        )));
+    }
+    if header.compute_checksum(&payload) != header.checksum {
+        return Err(Error::Io(format!("checksum mismatch in {:?} envelope: it is corrupted", header.kind)));
+    }
+    header.compression.decompress(payload, limit)
+}
+
+/// The CRC-32 used by zlib and PNG (reflected polynomial `0xedb88320`) of
+/// `parts` one after the other.
+pub(crate) fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in parts.iter().flat_map(|part| part.iter()) {
+        crc = CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// The lookup table of `crc32`, computed at compile time.
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn array8(bytes: &[u8]) -> [u8; 8] {
+    let mut array = [0; 8];
+    array.copy_from_slice(bytes);
+    array
+}
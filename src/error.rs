@@ -0,0 +1,87 @@
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::result;
+
+use libc::c_int;
+
+use ffi;
+
+/// An error reported by SEAL or by the safe wrappers around it.
+///
+/// The variants follow the C++ exception types thrown by SEAL, which the shim
+/// in `bindings.cpp` catches before they can cross into Rust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `std::invalid_argument`, or an argument rejected on the Rust side.
+    InvalidArgument(String),
+    /// `std::logic_error`, e.g. an operation on a transparent ciphertext.
+    LogicError(String),
+    /// `std::out_of_range`.
+    OutOfRange(String),
+    /// `std::ios_base::failure` while reading or writing a SEAL stream, or an
+    /// envelope that is truncated, corrupted or of an unknown format.
+    Io(String),
+    /// `std::runtime_error`.
+    Runtime(String),
+    /// `std::bad_alloc`.
+    OutOfMemory(String),
+    /// Any other exception.
+    Unknown(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    fn from_status(status: c_int, message: String) -> Error {
+        match status {
+            1 => Error::InvalidArgument(message),
+            2 => Error::LogicError(message),
+            3 => Error::OutOfRange(message),
+            4 => Error::Io(message),
+            5 => Error::Runtime(message),
+            6 => Error::OutOfMemory(message),
+            _ => Error::Unknown(message),
+        }
+    }
+
+    /// Returns the message attached to the error.
+    pub fn message(&self) -> &str {
+        match *self {
+            Error::InvalidArgument(ref m)
+            | Error::LogicError(ref m)
+            | Error::OutOfRange(ref m)
+            | Error::Io(ref m)
+            | Error::Runtime(ref m)
+            | Error::OutOfMemory(ref m)
+            | Error::Unknown(ref m) => m,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match *self {
+            Error::InvalidArgument(_) => "invalid argument",
+            Error::LogicError(_) => "logic error",
+            Error::OutOfRange(_) => "out of range",
+            Error::Io(_) => "I/O error",
+            Error::Runtime(_) => "runtime error",
+            Error::OutOfMemory(_) => "out of memory",
+            Error::Unknown(_) => "unknown error",
+        };
+        write!(f, "{}: {}", kind, self.message())
+    }
+}
+
+impl error::Error for Error {}
+
+/// Turns the status code returned by a shim function into a `Result`,
+/// picking up the message of the exception that caused it.
+pub(crate) fn check(status: c_int) -> Result<()> {
+    if status == 0 {
+        return Ok(());
+    }
+    let message = unsafe { CStr::from_ptr(ffi::bindings_LastError_message()) };
+    Err(Error::from_status(status, message.to_string_lossy().into_owned()))
+}
@@ -0,0 +1,645 @@
+use std::ptr;
+
+use ciphertext::Ciphertext;
+use context::Context;
+use error::{check, Error, Result};
+use ffi;
+use keys::{GaloisKeys, RelinKeys};
+use parameters::ParmsId;
+use plaintext::Plaintext;
+
+/// How far below a target scale `align` lets rescaling take a CKKS scale,
+/// relative to the target. Rescaling by different primes of the same bit size
+/// leaves scales this close.
+const RESCALE_TOLERANCE: f64 = 1e-3;
+
+/// Performs homomorphic operations on ciphertexts.
+///
+/// Every operation comes in two forms: `foo_inplace` overwrites its first
+/// argument with the result, while `foo` leaves its arguments untouched and
+/// returns the result as a new ciphertext.
+pub struct Evaluator {
+    ctx: Context,
+    ptr: *mut ffi::seal_Evaluator,
+}
+
+impl Evaluator {
+    pub fn new(ctx: &Context) -> Result<Evaluator> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_Create(ctx.as_ptr(), &mut ptr) })?;
+        Ok(Evaluator { ctx: ctx.clone(), ptr })
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn negate_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_negate_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn add_inplace(&self, encrypted1: &mut Ciphertext, encrypted2: &Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_add_inplace(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr()) })
+    }
+
+    pub fn multiply_inplace(&self, encrypted1: &mut Ciphertext, encrypted2: &Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_multiply_inplace(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr()) })
+    }
+
+    pub fn sub_inplace(&self, encrypted1: &mut Ciphertext, encrypted2: &Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_sub_inplace(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr()) })
+    }
+
+    pub fn square_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_square_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn relinearize_inplace(&self, encrypted: &mut Ciphertext, relin_keys: &RelinKeys) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_relinearize_inplace(self.ptr, encrypted.as_ptr(), relin_keys.as_ptr()) })
+    }
+
+    pub fn add_plain_inplace(&self, encrypted: &mut Ciphertext, plain: &Plaintext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_add_plain_inplace(self.ptr, encrypted.as_ptr(), plain.as_ptr()) })
+    }
+
+    pub fn sub_plain_inplace(&self, encrypted: &mut Ciphertext, plain: &Plaintext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_sub_plain_inplace(self.ptr, encrypted.as_ptr(), plain.as_ptr()) })
+    }
+
+    /// Multiplies a ciphertext by a plaintext. SEAL refuses a plaintext that is
+    /// zero, as the result would be a transparent ciphertext.
+    ///
+    /// A plaintext already transformed with `transform_plain_to_ntt` at the
+    /// level of the ciphertext can be used with a BFV ciphertext that is not
+    /// in NTT form: the ciphertext is transformed for the multiplication and
+    /// back afterwards, which is cheaper than transforming the plaintext.
+    pub fn multiply_plain_inplace(&self, encrypted: &mut Ciphertext, plain: &Plaintext) -> Result<()> {
+        let transform = check_ntt_forms(encrypted, plain)?;
+        if transform {
+            self.transform_to_ntt_inplace(encrypted)?;
+        }
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_multiply_plain_inplace(self.ptr, encrypted.as_ptr(), plain.as_ptr())
+        });
+        if transform {
+            self.transform_from_ntt_inplace(encrypted)?;
+        }
+        result
+    }
+
+    pub fn negate(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_negate(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn add(&self, encrypted1: &Ciphertext, encrypted2: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_add(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn sub(&self, encrypted1: &Ciphertext, encrypted2: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_sub(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn multiply(&self, encrypted1: &Ciphertext, encrypted2: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_multiply(self.ptr, encrypted1.as_ptr(), encrypted2.as_ptr(), &mut ptr)
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn square(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_square(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn relinearize(&self, encrypted: &Ciphertext, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_relinearize(self.ptr, encrypted.as_ptr(), relin_keys.as_ptr(), &mut ptr)
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Relinearizes a ciphertext only down to `destination_size`
+    /// polynomials, which must be at least 2 and at most the size of
+    /// `encrypted`. Relinearizing a ciphertext of size K + 1 needs keys for at
+    /// least K - 1 powers of the secret key.
+    pub fn relinearize_to(
+        &self,
+        encrypted: &Ciphertext,
+        relin_keys: &RelinKeys,
+        destination_size: usize,
+    ) -> Result<Ciphertext> {
+        let size = encrypted.size()?;
+        if destination_size < 2 || destination_size > size {
+            return Err(Error::InvalidArgument(format!(
+                "cannot relinearize a ciphertext of size {} to size {}",
+                size, destination_size
+            )));
+        }
+        if destination_size < size && relin_keys.size()? < size - 2 {
+            return Err(Error::InvalidArgument(format!(
+                "relinearizing a ciphertext of size {} needs {} relinearization keys, but there are only {}",
+                size,
+                size - 2,
+                relin_keys.size()?
+            )));
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_relinearize_to(
+                self.ptr,
+                encrypted.as_ptr(),
+                relin_keys.as_ptr(),
+                destination_size,
+                &mut ptr,
+            )
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn add_plain(&self, encrypted: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_add_plain(self.ptr, encrypted.as_ptr(), plain.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn sub_plain(&self, encrypted: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_sub_plain(self.ptr, encrypted.as_ptr(), plain.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    pub fn multiply_plain(&self, encrypted: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        if check_ntt_forms(encrypted, plain)? {
+            let mut encrypted_ntt = self.transform_to_ntt(encrypted)?;
+            check(unsafe {
+                ffi::bindings_Evaluator_multiply_plain_inplace(self.ptr, encrypted_ntt.as_ptr(), plain.as_ptr())
+            })?;
+            self.transform_from_ntt_inplace(&mut encrypted_ntt)?;
+            return Ok(encrypted_ntt);
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_multiply_plain(self.ptr, encrypted.as_ptr(), plain.as_ptr(), &mut ptr)
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Adds all the given ciphertexts together. Returns
+    /// `Error::InvalidArgument` if `encrypteds` is empty.
+    pub fn add_many(&self, encrypteds: &[Ciphertext]) -> Result<Ciphertext> {
+        let mut ptrs = raw_ciphertexts(encrypteds);
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_add_many(self.ptr, ptrs.as_mut_ptr(), ptrs.len(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Multiplies all the given ciphertexts together. SEAL multiplies them
+    /// pairwise in a balanced tree, relinearizing after every multiplication,
+    /// so the result has the least possible multiplicative depth. Only
+    /// supported with BFV.
+    pub fn multiply_many(&self, encrypteds: &[Ciphertext], relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        let mut ptrs = raw_ciphertexts(encrypteds);
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_multiply_many(
+                self.ptr,
+                ptrs.as_mut_ptr(),
+                ptrs.len(),
+                relin_keys.as_ptr(),
+                &mut ptr,
+            )
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Raises a ciphertext to a non-zero power, relinearizing after every
+    /// multiplication. Only supported with BFV.
+    pub fn exponentiate_inplace(
+        &self,
+        encrypted: &mut Ciphertext,
+        exponent: u64,
+        relin_keys: &RelinKeys,
+    ) -> Result<()> {
+        check(unsafe {
+            ffi::bindings_Evaluator_exponentiate_inplace(self.ptr, encrypted.as_ptr(), exponent, relin_keys.as_ptr())
+        })
+    }
+
+    pub fn exponentiate(&self, encrypted: &Ciphertext, exponent: u64, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_exponentiate(self.ptr, encrypted.as_ptr(), exponent, relin_keys.as_ptr(), &mut ptr)
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Rotates both rows of a batched BFV ciphertext cyclically by `steps`,
+    /// to the left when positive.
+    ///
+    /// Without a key for exactly `steps`, SEAL composes the rotation from the
+    /// power-of-two keys; if those are missing too, returns
+    /// `Error::InvalidArgument` naming the missing Galois element.
+    pub fn rotate_rows_inplace(&self, encrypted: &mut Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<()> {
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_rows_inplace(self.ptr, encrypted.as_ptr(), steps, galois_keys.as_ptr())
+        });
+        self.missing_key(result, galois_keys, steps)
+    }
+
+    pub fn rotate_rows(&self, encrypted: &Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_rows(self.ptr, encrypted.as_ptr(), steps, galois_keys.as_ptr(), &mut ptr)
+        });
+        self.missing_key(result, galois_keys, steps)?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Swaps the two rows of a batched BFV ciphertext.
+    pub fn rotate_columns_inplace(&self, encrypted: &mut Ciphertext, galois_keys: &GaloisKeys) -> Result<()> {
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_columns_inplace(self.ptr, encrypted.as_ptr(), galois_keys.as_ptr())
+        });
+        self.missing_key(result, galois_keys, 0)
+    }
+
+    pub fn rotate_columns(&self, encrypted: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_columns(self.ptr, encrypted.as_ptr(), galois_keys.as_ptr(), &mut ptr)
+        });
+        self.missing_key(result, galois_keys, 0)?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Rotates the slots of a CKKS ciphertext cyclically by `steps`, to the
+    /// left when positive. Missing keys are handled as in `rotate_rows`.
+    pub fn rotate_vector_inplace(
+        &self,
+        encrypted: &mut Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_vector_inplace(self.ptr, encrypted.as_ptr(), steps, galois_keys.as_ptr())
+        });
+        self.missing_key(result, galois_keys, steps)
+    }
+
+    pub fn rotate_vector(&self, encrypted: &Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_rotate_vector(self.ptr, encrypted.as_ptr(), steps, galois_keys.as_ptr(), &mut ptr)
+        });
+        self.missing_key(result, galois_keys, steps)?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Complex conjugates the slots of a CKKS ciphertext.
+    pub fn complex_conjugate_inplace(&self, encrypted: &mut Ciphertext, galois_keys: &GaloisKeys) -> Result<()> {
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_complex_conjugate_inplace(self.ptr, encrypted.as_ptr(), galois_keys.as_ptr())
+        });
+        self.missing_key(result, galois_keys, 0)
+    }
+
+    pub fn complex_conjugate(&self, encrypted: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        let result = check(unsafe {
+            ffi::bindings_Evaluator_complex_conjugate(self.ptr, encrypted.as_ptr(), galois_keys.as_ptr(), &mut ptr)
+        });
+        self.missing_key(result, galois_keys, 0)?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Applies the automorphism for a raw Galois element. Returns
+    /// `Error::InvalidArgument` if `galois_keys` has no key for it.
+    pub fn apply_galois_inplace(
+        &self,
+        encrypted: &mut Ciphertext,
+        galois_elt: u64,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        check_galois_key(galois_keys, galois_elt)?;
+        check(unsafe {
+            ffi::bindings_Evaluator_apply_galois_inplace(self.ptr, encrypted.as_ptr(), galois_elt, galois_keys.as_ptr())
+        })
+    }
+
+    pub fn apply_galois(
+        &self,
+        encrypted: &Ciphertext,
+        galois_elt: u64,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Ciphertext> {
+        check_galois_key(galois_keys, galois_elt)?;
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_apply_galois(
+                self.ptr,
+                encrypted.as_ptr(),
+                galois_elt,
+                galois_keys.as_ptr(),
+                &mut ptr,
+            )
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Switches a ciphertext to the next level of the modulus switching chain
+    /// without scaling its values, dropping the last coefficient modulus prime.
+    pub fn mod_switch_to_next_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_to_next_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn mod_switch_to_next(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_to_next(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Switches a ciphertext down the modulus switching chain to the level
+    /// with the given parms_id, which must not be above its current level.
+    pub fn mod_switch_to_inplace(&self, encrypted: &mut Ciphertext, parms_id: ParmsId) -> Result<()> {
+        let words = parms_id.words();
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_to_inplace(self.ptr, encrypted.as_ptr(), words.as_ptr()) })
+    }
+
+    pub fn mod_switch_to(&self, encrypted: &Ciphertext, parms_id: ParmsId) -> Result<Ciphertext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_mod_switch_to(self.ptr, encrypted.as_ptr(), words.as_ptr(), &mut ptr)
+        })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Switches a plaintext in NTT form, such as a CKKS plaintext, to the next
+    /// level of the modulus switching chain.
+    pub fn mod_switch_plain_to_next_inplace(&self, plain: &mut Plaintext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_plain_to_next_inplace(self.ptr, plain.as_ptr()) })
+    }
+
+    pub fn mod_switch_plain_to_next(&self, plain: &Plaintext) -> Result<Plaintext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_plain_to_next(self.ptr, plain.as_ptr(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Switches a plaintext in NTT form down to the level with the given
+    /// parms_id.
+    pub fn mod_switch_plain_to_inplace(&self, plain: &mut Plaintext, parms_id: ParmsId) -> Result<()> {
+        let words = parms_id.words();
+        check(unsafe { ffi::bindings_Evaluator_mod_switch_plain_to_inplace(self.ptr, plain.as_ptr(), words.as_ptr()) })
+    }
+
+    pub fn mod_switch_plain_to(&self, plain: &Plaintext, parms_id: ParmsId) -> Result<Plaintext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_mod_switch_plain_to(self.ptr, plain.as_ptr(), words.as_ptr(), &mut ptr)
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Switches a CKKS ciphertext to the next level of the modulus switching
+    /// chain, dividing its values and its scale by the dropped prime.
+    pub fn rescale_to_next_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_rescale_to_next_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn rescale_to_next(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_rescale_to_next(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Rescales a CKKS ciphertext down to the level with the given parms_id,
+    /// dividing by every prime dropped on the way.
+    pub fn rescale_to_inplace(&self, encrypted: &mut Ciphertext, parms_id: ParmsId) -> Result<()> {
+        let words = parms_id.words();
+        check(unsafe { ffi::bindings_Evaluator_rescale_to_inplace(self.ptr, encrypted.as_ptr(), words.as_ptr()) })
+    }
+
+    pub fn rescale_to(&self, encrypted: &Ciphertext, parms_id: ParmsId) -> Result<Ciphertext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_rescale_to(self.ptr, encrypted.as_ptr(), words.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Transforms a BFV plaintext to NTT form for the level with the given
+    /// parms_id, so that it can be multiplied with ciphertexts at that level
+    /// without being transformed again every time.
+    pub fn transform_plain_to_ntt_inplace(&self, plain: &mut Plaintext, parms_id: ParmsId) -> Result<()> {
+        let words = parms_id.words();
+        check(unsafe {
+            ffi::bindings_Evaluator_transform_plain_to_ntt_inplace(self.ptr, plain.as_ptr(), words.as_ptr())
+        })
+    }
+
+    pub fn transform_plain_to_ntt(&self, plain: &Plaintext, parms_id: ParmsId) -> Result<Plaintext> {
+        let words = parms_id.words();
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_Evaluator_transform_plain_to_ntt(self.ptr, plain.as_ptr(), words.as_ptr(), &mut ptr)
+        })?;
+        Ok(Plaintext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Transforms a BFV ciphertext to NTT form.
+    pub fn transform_to_ntt_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_transform_to_ntt_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn transform_to_ntt(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_transform_to_ntt(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Transforms a BFV ciphertext in NTT form back to coefficient form,
+    /// which decryption and most other operations require.
+    pub fn transform_from_ntt_inplace(&self, encrypted: &mut Ciphertext) -> Result<()> {
+        check(unsafe { ffi::bindings_Evaluator_transform_from_ntt_inplace(self.ptr, encrypted.as_ptr()) })
+    }
+
+    pub fn transform_from_ntt(&self, encrypted: &Ciphertext) -> Result<Ciphertext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Evaluator_transform_from_ntt(self.ptr, encrypted.as_ptr(), &mut ptr) })?;
+        Ok(Ciphertext::from_ptr(&self.ctx, ptr))
+    }
+
+    /// Mod switches whichever of the two ciphertexts is higher in the
+    /// modulus switching chain down to the level of the other, so that they
+    /// can be added or multiplied.
+    pub fn align_levels(&self, encrypted1: &mut Ciphertext, encrypted2: &mut Ciphertext) -> Result<()> {
+        let (parms_id1, parms_id2) = (encrypted1.parms_id()?, encrypted2.parms_id()?);
+        let index1 = self.ctx.context_data(parms_id1)?.chain_index()?;
+        let index2 = self.ctx.context_data(parms_id2)?.chain_index()?;
+        if index1 < index2 {
+            self.mod_switch_to_inplace(encrypted1, parms_id2)
+        } else if index2 < index1 {
+            self.mod_switch_to_inplace(encrypted2, parms_id1)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Brings two CKKS ciphertexts to the same level and scale, so that they
+    /// can be added as well as multiplied.
+    ///
+    /// A ciphertext whose scale is larger than the other's by at least the
+    /// prime rescaling would drop, e.g. the unrescaled product of two
+    /// ciphertexts, is rescaled first. Both are then brought to the same level
+    /// with `align_levels`.
+    ///
+    /// Rescaling by different primes leaves scales that are close but not
+    /// equal. If their relative difference is at most `tolerance`, the scale
+    /// of `encrypted2` is set to that of `encrypted1`, which changes its values
+    /// by the same relative amount; pass 0.0 to only accept equal scales.
+    /// Otherwise returns `Error::InvalidArgument` and leaves both ciphertexts
+    /// unchanged.
+    pub fn align(&self, encrypted1: &mut Ciphertext, encrypted2: &mut Ciphertext, tolerance: f64) -> Result<()> {
+        if tolerance.is_nan() || tolerance < 0.0 {
+            return Err(Error::InvalidArgument(format!("tolerance {} is not a non-negative number", tolerance)));
+        }
+        let (parms_id1, scale1) = self.rescale_target(encrypted1, encrypted2.scale()?)?;
+        let (parms_id2, scale2) = self.rescale_target(encrypted2, scale1)?;
+        if scale1 != scale2 && !scales_close(scale1, scale2, tolerance) {
+            return Err(Error::InvalidArgument(format!(
+                "cannot align ciphertexts with scales {} and {}",
+                scale1, scale2
+            )));
+        }
+
+        // Both results are computed out of place, so that neither ciphertext
+        // changes unless every step succeeds.
+        let index1 = self.ctx.context_data(parms_id1)?.chain_index()?;
+        let index2 = self.ctx.context_data(parms_id2)?.chain_index()?;
+        let target = if index1 < index2 { parms_id1 } else { parms_id2 };
+        let aligned1 = self.aligned_copy(encrypted1, parms_id1, target)?;
+        let mut aligned2 = self.aligned_copy(encrypted2, parms_id2, target)?;
+        if scale1 != scale2 {
+            match aligned2 {
+                Some(ref mut aligned) => aligned.set_scale(scale1)?,
+                None => encrypted2.set_scale(scale1)?,
+            }
+        }
+        if let Some(aligned) = aligned1 {
+            *encrypted1 = aligned;
+        }
+        if let Some(aligned) = aligned2 {
+            *encrypted2 = aligned;
+        }
+        Ok(())
+    }
+
+    /// Returns `encrypted` rescaled to `rescaled` and then mod switched to
+    /// `target`, or `None` if it is already at `target`.
+    fn aligned_copy(&self, encrypted: &Ciphertext, rescaled: ParmsId, target: ParmsId) -> Result<Option<Ciphertext>> {
+        let mut copy = None;
+        if rescaled != encrypted.parms_id()? {
+            copy = Some(self.rescale_to(encrypted, rescaled)?);
+        }
+        if target != rescaled {
+            copy = Some(self.mod_switch_to(copy.as_ref().unwrap_or(encrypted), target)?);
+        }
+        Ok(copy)
+    }
+
+    /// Returns the parms_id and scale `encrypted` would have after rescaling
+    /// for as long as that does not take its scale noticeably below `scale`
+    /// and there is a level left to rescale to.
+    fn rescale_target(&self, encrypted: &Ciphertext, scale: f64) -> Result<(ParmsId, f64)> {
+        let (mut parms_id, mut current) = (encrypted.parms_id()?, encrypted.scale()?);
+        loop {
+            let data = self.ctx.context_data(parms_id)?;
+            let next = match data.next()? {
+                Some(next) => next,
+                None => return Ok((parms_id, current)),
+            };
+            let prime = match data.coeff_modulus()?.last() {
+                Some(prime) => prime.value() as f64,
+                None => return Ok((parms_id, current)),
+            };
+            let rescaled = current / prime;
+            if rescaled < scale && !scales_close(rescaled, scale, RESCALE_TOLERANCE) {
+                return Ok((parms_id, current));
+            }
+            parms_id = next.parms_id();
+            current = rescaled;
+        }
+    }
+
+    /// Replaces SEAL's terse error for a failed rotation by `steps` with one
+    /// naming the missing Galois element, when a key is indeed missing.
+    fn missing_key(&self, result: Result<()>, galois_keys: &GaloisKeys, steps: i32) -> Result<()> {
+        let message = match result {
+            Err(Error::InvalidArgument(message)) => message,
+            other => return other,
+        };
+        let degree = self.ctx.first_context_data()?.parms()?.poly_modulus_degree()?;
+        match GaloisKeys::galois_elt_from_step(steps, degree) {
+            Ok(galois_elt) if !galois_keys.has_key(galois_elt)? => Err(Error::InvalidArgument(format!(
+                "no Galois key for galois element {} (rotation by {} steps): {}",
+                galois_elt, steps, message
+            ))),
+            _ => Err(Error::InvalidArgument(message)),
+        }
+    }
+}
+
+/// Checks that `plain` can be multiplied with `encrypted`, and returns whether
+/// `encrypted` has to be transformed to NTT form for it.
+fn check_ntt_forms(encrypted: &Ciphertext, plain: &Plaintext) -> Result<bool> {
+    let encrypted_ntt = encrypted.is_ntt_form()?;
+    if !plain.is_ntt_form()? {
+        if encrypted_ntt {
+            return Err(Error::InvalidArgument(
+                "the ciphertext is in NTT form but the plaintext is not".to_owned(),
+            ));
+        }
+        return Ok(false);
+    }
+    let (plain_parms_id, encrypted_parms_id) = (plain.parms_id()?, encrypted.parms_id()?);
+    if plain_parms_id != encrypted_parms_id {
+        return Err(Error::InvalidArgument(format!(
+            "the plaintext is in NTT form for parms_id {} but the ciphertext is at parms_id {}",
+            plain_parms_id, encrypted_parms_id
+        )));
+    }
+    Ok(!encrypted_ntt)
+}
+
+fn scales_close(scale1: f64, scale2: f64, tolerance: f64) -> bool {
+    (scale1 - scale2).abs() <= tolerance * scale1.max(scale2)
+}
+
+fn check_galois_key(galois_keys: &GaloisKeys, galois_elt: u64) -> Result<()> {
+    if galois_keys.has_key(galois_elt)? {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(format!("no Galois key for galois element {}", galois_elt)))
+    }
+}
+
+fn raw_ciphertexts(encrypteds: &[Ciphertext]) -> Vec<*const ffi::seal_Ciphertext> {
+    encrypteds.iter().map(|ct| ct.as_ptr() as *const _).collect()
+}
+
+impl Drop for Evaluator {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_Evaluator_Destroy(self.ptr) }
+    }
+}
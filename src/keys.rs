@@ -0,0 +1,382 @@
+use std::io::{Read, Write};
+use std::ptr;
+
+use context::Context;
+use envelope::{Compression, ObjectKind};
+use error::{check, Error, Result};
+use ffi;
+use parameters::{ParmsId, SchemeType};
+use serialization::{self, Serializable};
+
+/// Generates the secret and public keys for a context, along with the
+/// relinearization and Galois keys derived from them.
+pub struct KeyGenerator {
+    ctx: Context,
+    ptr: *mut ffi::seal_KeyGenerator,
+}
+
+impl KeyGenerator {
+    /// Creates a key generator, which immediately generates a fresh secret and
+    /// public key pair.
+    pub fn new(ctx: &Context) -> Result<KeyGenerator> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_KeyGenerator_Create(ctx.as_ptr(), &mut ptr) })?;
+        Ok(KeyGenerator { ctx: ctx.clone(), ptr })
+    }
+
+    /// Returns the context this object was created with.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_KeyGenerator_public_key(self.ptr, &mut ptr) })?;
+        Ok(PublicKey { ctx: self.ctx.clone(), ptr })
+    }
+
+    /// Returns the smallest decomposition bit count SEAL accepts for
+    /// relinearization and Galois keys (`DefaultParams::dbc_min()`).
+    pub fn dbc_min() -> Result<i32> {
+        let mut dbc = 0;
+        check(unsafe { ffi::bindings_DefaultParams_dbc_min(&mut dbc) })?;
+        Ok(dbc)
+    }
+
+    /// Returns the largest decomposition bit count SEAL accepts for
+    /// relinearization and Galois keys (`DefaultParams::dbc_max()`).
+    pub fn dbc_max() -> Result<i32> {
+        let mut dbc = 0;
+        check(unsafe { ffi::bindings_DefaultParams_dbc_max(&mut dbc) })?;
+        Ok(dbc)
+    }
+
+    pub fn secret_key(&self) -> Result<SecretKey> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_KeyGenerator_secret_key(self.ptr, &mut ptr) })?;
+        Ok(SecretKey { ctx: self.ctx.clone(), ptr })
+    }
+
+    /// Generates relinearization keys for the powers 2 to `count + 1` of the
+    /// secret key, so that ciphertexts of size up to `count + 2` can be
+    /// relinearized. The decomposition bit count must lie between `dbc_min()`
+    /// and `dbc_max()`.
+    pub fn relin_keys(&self, decomposition_bit_count: i32, count: i32) -> Result<RelinKeys> {
+        check_decomposition_bit_count(decomposition_bit_count)?;
+        if count < 1 {
+            return Err(Error::InvalidArgument(format!("relinearization key count {} is not positive", count)));
+        }
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_KeyGenerator_relin_keys(self.ptr, decomposition_bit_count, count, &mut ptr)
+        })?;
+        Ok(RelinKeys { ctx: self.ctx.clone(), ptr })
+    }
+
+    /// Generates Galois keys for every power-of-two rotation step.
+    pub fn galois_keys(&self, decomposition_bit_count: i32) -> Result<GaloisKeys> {
+        check_decomposition_bit_count(decomposition_bit_count)?;
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_KeyGenerator_galois_keys(self.ptr, decomposition_bit_count, &mut ptr) })?;
+        Ok(GaloisKeys { ctx: self.ctx.clone(), ptr })
+    }
+
+    /// Generates Galois keys for rotating rows (BFV) or vectors (CKKS) by
+    /// exactly the given steps, where positive steps rotate left. A step of 0
+    /// generates the key for `rotate_columns` and `complex_conjugate`.
+    ///
+    /// This is much smaller than `galois_keys` when only a few rotations are
+    /// needed.
+    pub fn galois_keys_for_steps(&self, decomposition_bit_count: i32, steps: &[i32]) -> Result<GaloisKeys> {
+        let degree = self.ctx.first_context_data()?.parms()?.poly_modulus_degree()?;
+        let galois_elts = steps
+            .iter()
+            .map(|&step| GaloisKeys::galois_elt_from_step(step, degree))
+            .collect::<Result<Vec<_>>>()?;
+        self.galois_keys_for_elts(decomposition_bit_count, &galois_elts)
+    }
+
+    /// Generates Galois keys for the given raw Galois elements, which must be
+    /// odd and smaller than twice the polynomial modulus degree.
+    pub fn galois_keys_for_elts(&self, decomposition_bit_count: i32, galois_elts: &[u64]) -> Result<GaloisKeys> {
+        check_decomposition_bit_count(decomposition_bit_count)?;
+        let mut ptr = ptr::null_mut();
+        check(unsafe {
+            ffi::bindings_KeyGenerator_galois_keys_elts(
+                self.ptr,
+                decomposition_bit_count,
+                galois_elts.as_ptr(),
+                galois_elts.len(),
+                &mut ptr,
+            )
+        })?;
+        Ok(GaloisKeys { ctx: self.ctx.clone(), ptr })
+    }
+}
+
+fn check_decomposition_bit_count(decomposition_bit_count: i32) -> Result<()> {
+    let (min, max) = (KeyGenerator::dbc_min()?, KeyGenerator::dbc_max()?);
+    if decomposition_bit_count < min || decomposition_bit_count > max {
+        return Err(Error::InvalidArgument(format!(
+            "decomposition bit count {} is not between {} and {}",
+            decomposition_bit_count, min, max
+        )));
+    }
+    Ok(())
+}
+
+impl Drop for KeyGenerator {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_KeyGenerator_Destroy(self.ptr) }
+    }
+}
+
+pub struct PublicKey {
+    ctx: Context,
+    ptr: *mut ffi::seal_PublicKey,
+}
+
+impl PublicKey {
+    /// Writes the key to `writer` in SEAL's binary format, wrapped in an
+    /// envelope.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Reads a key written by `save`, checking that it is valid for `ctx`.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<PublicKey> {
+        serialization::load(ctx, reader)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_PublicKey {
+        self.ptr
+    }
+}
+
+impl Serializable for PublicKey {
+    const KIND: ObjectKind = ObjectKind::PublicKey;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.ctx.first_parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_PublicKey_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<PublicKey> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_PublicKey_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(PublicKey { ctx: ctx.clone(), ptr })
+    }
+}
+
+impl Drop for PublicKey {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_PublicKey_Destroy(self.ptr) }
+    }
+}
+
+pub struct SecretKey {
+    ctx: Context,
+    ptr: *mut ffi::seal_SecretKey,
+}
+
+impl SecretKey {
+    /// Writes the key to `writer` in SEAL's binary format, wrapped in an
+    /// envelope. Anyone holding the result can decrypt.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Reads a key written by `save`, checking that it is valid for `ctx`.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<SecretKey> {
+        serialization::load(ctx, reader)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_SecretKey {
+        self.ptr
+    }
+}
+
+impl Serializable for SecretKey {
+    const KIND: ObjectKind = ObjectKind::SecretKey;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.ctx.first_parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_SecretKey_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<SecretKey> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_SecretKey_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(SecretKey { ctx: ctx.clone(), ptr })
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_SecretKey_Destroy(self.ptr) }
+    }
+}
+
+/// Relinearization keys, one for each power of the secret key from 2 up to
+/// `size() + 1`.
+pub struct RelinKeys {
+    ctx: Context,
+    ptr: *mut ffi::seal_RelinKeys,
+}
+
+impl RelinKeys {
+    /// Returns the number of secret key powers there are keys for.
+    pub fn size(&self) -> Result<usize> {
+        let mut size = 0;
+        check(unsafe { ffi::bindings_RelinKeys_size(self.ptr, &mut size) })?;
+        Ok(size)
+    }
+
+    pub fn decomposition_bit_count(&self) -> Result<i32> {
+        let mut dbc = 0;
+        check(unsafe { ffi::bindings_RelinKeys_decomposition_bit_count(self.ptr, &mut dbc) })?;
+        Ok(dbc)
+    }
+
+    /// Returns the parms_id of the parameters the keys were generated for,
+    /// which is always the first one in the chain.
+    pub fn parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_RelinKeys_parms_id(self.ptr, words.as_mut_ptr()) })?;
+        Ok(ParmsId::new(words))
+    }
+
+    /// Writes the keys to `writer` in SEAL's binary format, wrapped in an
+    /// envelope.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Like `save`, but compresses the payload. `load` detects and undoes the
+    /// compression.
+    pub fn save_compressed(&self, writer: &mut impl Write, compression: Compression) -> Result<()> {
+        serialization::save_compressed(self, writer, compression)
+    }
+
+    /// Reads keys written by `save`, checking that they are valid for `ctx`.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<RelinKeys> {
+        serialization::load(ctx, reader)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_RelinKeys {
+        self.ptr
+    }
+}
+
+impl Serializable for RelinKeys {
+    const KIND: ObjectKind = ObjectKind::RelinKeys;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_RelinKeys_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<RelinKeys> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_RelinKeys_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(RelinKeys { ctx: ctx.clone(), ptr })
+    }
+}
+
+impl Drop for RelinKeys {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_RelinKeys_Destroy(self.ptr) }
+    }
+}
+
+pub struct GaloisKeys {
+    ctx: Context,
+    ptr: *mut ffi::seal_GaloisKeys,
+}
+
+impl GaloisKeys {
+    /// Returns whether there is a key for the given Galois element.
+    pub fn has_key(&self, galois_elt: u64) -> Result<bool> {
+        let mut has_key = false;
+        check(unsafe { ffi::bindings_GaloisKeys_has_key(self.ptr, galois_elt, &mut has_key) })?;
+        Ok(has_key)
+    }
+
+    /// Returns the Galois element SEAL uses to rotate rows (BFV) or vectors
+    /// (CKKS) by `steps`, where positive steps rotate left. A step of 0 gives
+    /// the element for `rotate_columns` and `complex_conjugate`.
+    pub fn galois_elt_from_step(steps: i32, poly_modulus_degree: usize) -> Result<u64> {
+        let m = 2 * poly_modulus_degree as u64;
+        if steps == 0 {
+            return Ok(m - 1);
+        }
+        let row_size = poly_modulus_degree as u64 / 2;
+        let pos_steps = u64::from(steps.wrapping_abs() as u32);
+        if pos_steps >= row_size {
+            return Err(Error::InvalidArgument(format!(
+                "step count {} is too large for {} slots per row",
+                steps, row_size
+            )));
+        }
+        let exponent = if steps < 0 { row_size - pos_steps } else { pos_steps };
+        let mut galois_elt = 1u64;
+        for _ in 0..exponent {
+            galois_elt = galois_elt * 3 % m;
+        }
+        Ok(galois_elt)
+    }
+
+    /// Writes the keys to `writer` in SEAL's binary format, wrapped in an
+    /// envelope.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Like `save`, but compresses the payload. `load` detects and undoes the
+    /// compression.
+    pub fn save_compressed(&self, writer: &mut impl Write, compression: Compression) -> Result<()> {
+        serialization::save_compressed(self, writer, compression)
+    }
+
+    /// Reads keys written by `save`, checking that they are valid for `ctx`.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<GaloisKeys> {
+        serialization::load(ctx, reader)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_GaloisKeys {
+        self.ptr
+    }
+}
+
+impl Serializable for GaloisKeys {
+    const KIND: ObjectKind = ObjectKind::GaloisKeys;
+
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        Ok((serialization::context_scheme(&self.ctx)?, self.ctx.first_parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_GaloisKeys_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<GaloisKeys> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_GaloisKeys_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(GaloisKeys { ctx: ctx.clone(), ptr })
+    }
+}
+
+impl Drop for GaloisKeys {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_GaloisKeys_Destroy(self.ptr) }
+    }
+}
@@ -1,681 +1,2050 @@
-#![allow(non_upper_case_globals)]
-#![allow(non_camel_case_types)]
-#![allow(non_snake_case)]
-
-include!("./bindings.rs");
-
 extern crate libc;
+extern crate num_complex;
+extern crate sha3;
+#[cfg(feature = "serde")]
+extern crate base64;
+#[cfg(feature = "deflate")]
+extern crate flate2;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "zstd")]
+extern crate zstd;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+/// Raw bindgen output for SEAL and the C shim in `bindings.cpp`.
+#[allow(non_upper_case_globals)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+pub mod ffi {
+    include!("./bindings.rs");
+}
 
-use std::ffi::CStr;
-use std::ffi::CString;
+mod biguint;
+mod builder;
+mod ciphertext;
+mod context;
+mod decryptor;
+mod encoders;
+mod encryptor;
+pub mod envelope;
+mod error;
+mod evaluator;
+mod keys;
+mod modulus;
+mod parameters;
+mod plaintext;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod serialization;
+mod slot_matrix;
+
+pub use biguint::BigUInt;
+pub use builder::{BfvParametersBuilder, CkksParametersBuilder};
+pub use ciphertext::{Ciphertext, SeededCiphertext};
+pub use context::{Context, ContextChain, ContextData, EncryptionParameterQualifiers};
+pub use decryptor::Decryptor;
+pub use encoders::{BatchEncoder, CkksEncoder, IntegerEncoder};
+pub use encryptor::Encryptor;
+pub use error::{Error, Result};
+pub use evaluator::Evaluator;
+pub use keys::{GaloisKeys, KeyGenerator, PublicKey, RelinKeys, SecretKey};
+pub use modulus::SmallModulus;
+pub use num_complex::Complex;
+pub use parameters::{EncryptionParameters, ParmsId, SchemeType, SecurityLevel};
+pub use plaintext::Plaintext;
+#[cfg(feature = "serde")]
+pub use serde_support::ContextSeed;
+pub use slot_matrix::SlotMatrix;
 
 #[test]
-fn example_bfv_basics_i() {
+fn example_bfv_basics_i() -> Result<()> {
     // This means to emulate https://github.com/Microsoft/SEAL/blob/master/examples/examples.cpp
-    unsafe {
-        println!("Example: BFV Basics I");
-
-        /*
-        In this example we demonstrate setting up encryption parameters and other 
-        relevant objects for performing simple computations on encrypted integers.
-        Microsoft SEAL implements two encryption schemes: the Brakerski/Fan-Vercauteren (BFV) 
-        scheme and the Cheon-Kim-Kim-Song (CKKS) scheme. In the first examples we 
-        use the BFV scheme as it is far easier to understand and use than CKKS. For 
-        more details on the basics of the BFV scheme, we refer the reader to the
-        original paper https://eprint.iacr.org/2012/144. In truth, to achieve good 
-        performance Microsoft SEAL implements the "FullRNS" optimization as described in 
-        https://eprint.iacr.org/2016/510, but this optimization is invisible to 
-        the user and has no security implications. We will discuss the CKKS scheme
-        in later examples.
-        The first task is to set up an instance of the EncryptionParameters class.
-        It is critical to understand how these different parameters behave, how they
-        affect the encryption scheme, performance, and the security level. There are 
-        three encryption parameters that are necessary to set: 
-            - poly_modulus_degree (degree of polynomial modulus);
-            - coeff_modulus ([ciphertext] coefficient modulus);
-            - plain_modulus (plaintext modulus).
-        A fourth parameter -- noise_standard_deviation -- has a default value 3.20 
-        and should not be necessary to modify unless the user has a specific reason 
-        to do so and has an in-depth understanding of the security implications.
-        A fifth parameter -- random_generator -- can be set to use customized random
-        number generators. By default, Microsoft SEAL uses hardware-based AES in counter mode
-        for pseudo-randomness with key generated using std::random_device. If the 
-        AES-NI instruction set is not available, all randomness is generated from 
-        std::random_device. Most academic users in particular should have little 
-        reason to change this.
-        The BFV scheme cannot perform arbitrary computations on encrypted data. 
-        Instead, each ciphertext has a specific quantity called the `invariant noise 
-        budget' -- or `noise budget' for short -- measured in bits. The noise budget 
-        in a freshly encrypted ciphertext (initial noise budget) is determined by 
-        the encryption parameters. Homomorphic operations consume the noise budget 
-        at a rate also determined by the encryption parameters. In BFV the two basic 
-        operations allowed on encrypted data are additions and multiplications, of 
-        which additions can generally be thought of as being nearly free in terms of 
-        noise budget consumption compared to multiplications. Since noise budget 
-        consumption compounds in sequential multiplications, the most significant 
-        factor in choosing appropriate encryption parameters is the multiplicative 
-        depth of the arithmetic circuit that the user wants to evaluate on encrypted
-        data. Once the noise budget of a ciphertext reaches zero it becomes too 
-        corrupted to be decrypted. Thus, it is essential to choose the parameters to 
-        be large enough to support the desired computation; otherwise the result is 
-        impossible to make sense of even with the secret key.
-        */
-        let mut ep = bindings_EncryptionParameters_Create(1);
-
-        /*
-        The first parameter we set is the degree of the polynomial modulus. This must
-        be a positive power of 2, representing the degree of a power-of-2 cyclotomic 
-        polynomial; it is not necessary to understand what this means. The polynomial 
-        modulus degree should be thought of mainly affecting the security level of the 
-        scheme: larger degree makes the scheme more secure. Larger degree also makes 
-        ciphertext sizes larger, and consequently all operations slower. Recommended 
-        degrees are 1024, 2048, 4096, 8192, 16384, 32768, but it is also possible to 
-        go beyond this. In this example we use a relatively small polynomial modulus.
-        */
-        bindings_EncryptionParameters_set_poly_modulus_degree(ep, 2048);
-
-        /*
-        Next we set the [ciphertext] coefficient modulus (coeff_modulus). The size 
-        of the coefficient modulus should be thought of as the most significant 
-        factor in determining the noise budget in a freshly encrypted ciphertext: 
-        bigger means more noise budget, which is desirable. On the other hand, 
-        a larger coefficient modulus lowers the security level of the scheme. Thus, 
-        if a large noise budget is required for complicated computations, a large 
-        coefficient modulus needs to be used, and the reduction in the security 
-        level must be countered by simultaneously increasing the polynomial modulus. 
-        Overall, this will result in worse performance.
-        
-        To make parameter selection easier for the user, we have constructed sets 
-        of largest safe coefficient moduli for 128-bit and 192-bit security levels
-        for different choices of the polynomial modulus. These default parameters 
-        follow the recommendations in the Security Standard Draft available at 
-        http://HomomorphicEncryption.org. The security estimates are a complicated
-        topic and we highly recommend consulting with experts in the field when 
-        selecting parameters. 
-        Our recommended values for the coefficient modulus can be easily accessed 
-        through the functions 
-            
-            DefaultParams::coeff_modulus_128(int)
-            DefaultParams::coeff_modulus_192(int)
-            DefaultParams::coeff_modulus_256(int)
-        for 128-bit, 192-bit, and 256-bit security levels. The integer parameter is 
-        the degree of the polynomial modulus used.
-        
-        In Microsoft SEAL the coefficient modulus is a positive composite number -- 
-        a product of distinct primes of size up to 60 bits. When we talk about the size 
-        of the coefficient modulus we mean the bit length of the product of the primes. 
-        The small primes are represented by instances of the SmallModulus class so for
-        example DefaultParams::coeff_modulus_128(int) returns a vector of SmallModulus 
-        instances. 
-        
-        It is possible for the user to select their own small primes. Since Microsoft 
-        SEAL uses the Number Theoretic Transform (NTT) for polynomial multiplications 
-        modulo the factors of the coefficient modulus, the factors need to be prime 
-        numbers congruent to 1 modulo 2*poly_modulus_degree. We have generated a list 
-        of such prime numbers of various sizes that the user can easily access through 
-        the functions 
-        
-            DefaultParams::small_mods_60bit(int)
-            DefaultParams::small_mods_50bit(int)
-            DefaultParams::small_mods_40bit(int)
-            DefaultParams::small_mods_30bit(int)
-        
-        each of which gives access to an array of primes of the denoted size. These 
-        primes are located in the source file util/globals.cpp. Again, please keep 
-        in mind that the choice of coeff_modulus has a dramatic effect on security 
-        and should almost always be obtained through coeff_modulus_xxx(int).
-        Performance is mainly affected by the size of the polynomial modulus, and 
-        the number of prime factors in the coefficient modulus; hence in some cases
-        it can be important to use as few prime factors in the coefficient modulus 
-        as possible.
-        In this example we use the default coefficient modulus for a 128-bit security
-        level. Concretely, this coefficient modulus consists of only one 54-bit prime 
-        factor: 0x3fffffff000001.
-        */
-        bindings_EncryptionParameters_set_coeff_modulus(ep, 128, 2048);
-
-        /*
-        The plaintext modulus can be any positive integer, even though here we take 
-        it to be a power of two. In fact, in many cases one might instead want it 
-        to be a prime number; we will see this in later examples. The plaintext 
-        modulus determines the size of the plaintext data type but it also affects 
-        the noise budget in a freshly encrypted ciphertext and the consumption of
-        noise budget in homomorphic (encrypted) multiplications. Thus, it is 
-        essential to try to keep the plaintext data type as small as possible for 
-        best performance. The noise budget in a freshly encrypted ciphertext is 
-        
-            ~ log2(coeff_modulus/plain_modulus) (bits)
-        and the noise budget consumption in a homomorphic multiplication is of the 
-        form log2(plain_modulus) + (other terms).
-        */
-        bindings_EncryptionParameters_set_plain_modulus(ep, 256);
-
-        /*
-        Now that all parameters are set, we are ready to construct a SEALContext 
-        object. This is a heavy class that checks the validity and properties of the 
-        parameters we just set and performs several important pre-computations.
-        */
-        let mut ctx = bindings_SEALContext_Create(ep, false);
-
-        /*
-        Plaintexts in the BFV scheme are polynomials with coefficients integers 
-        modulo plain_modulus. This is not a very practical object to encrypt: much
-        more useful would be encrypting integers or floating point numbers. For this
-        we need an `encoding scheme' to convert data from integer representation to
-        an appropriate plaintext polynomial representation than can subsequently be 
-        encrypted. Microsoft SEAL comes with a few basic encoders for the BFV scheme:
-        [IntegerEncoder]
-        The IntegerEncoder encodes integers to plaintext polynomials as follows. 
-        First, a binary expansion of the integer is computed. Next, a polynomial is
-        created with the bits as coefficients. For example, the integer 
-        
-            26 = 2^4 + 2^3 + 2^1
-        
-        is encoded as the polynomial 1x^4 + 1x^3 + 1x^1. Conversely, plaintext
-        polynomials are decoded by evaluating them at x=2. For negative numbers the
-        IntegerEncoder simply stores all coefficients as either 0 or -1, where -1 is
-        represented by the unsigned integer plain_modulus - 1 in memory.
-        Since encrypted computations operate on the polynomials rather than on the
-        encoded integers themselves, the polynomial coefficients will grow in the
-        course of such computations. For example, computing the sum of the encrypted
-        encoded integer 26 with itself will result in an encrypted polynomial with
-        larger coefficients: 2x^4 + 2x^3 + 2x^1. Squaring the encrypted encoded
-        integer 26 results also in increased coefficients due to cross-terms, namely,
+    println!("Example: BFV Basics I");
+
+    /*
+    In this example we demonstrate setting up encryption parameters and other 
+    relevant objects for performing simple computations on encrypted integers.
+    Microsoft SEAL implements two encryption schemes: the Brakerski/Fan-Vercauteren (BFV) 
+    scheme and the Cheon-Kim-Kim-Song (CKKS) scheme. In the first examples we 
+    use the BFV scheme as it is far easier to understand and use than CKKS. For 
+    more details on the basics of the BFV scheme, we refer the reader to the
+    original paper https://eprint.iacr.org/2012/144. In truth, to achieve good 
+    performance Microsoft SEAL implements the "FullRNS" optimization as described in 
+    https://eprint.iacr.org/2016/510, but this optimization is invisible to 
+    the user and has no security implications. We will discuss the CKKS scheme
+    in later examples.
+    The first task is to set up an instance of the EncryptionParameters class.
+    It is critical to understand how these different parameters behave, how they
+    affect the encryption scheme, performance, and the security level. There are 
+    three encryption parameters that are necessary to set: 
+        - poly_modulus_degree (degree of polynomial modulus);
+        - coeff_modulus ([ciphertext] coefficient modulus);
+        - plain_modulus (plaintext modulus).
+    A fourth parameter -- noise_standard_deviation -- has a default value 3.20 
+    and should not be necessary to modify unless the user has a specific reason 
+    to do so and has an in-depth understanding of the security implications.
+    A fifth parameter -- random_generator -- can be set to use customized random
+    number generators. By default, Microsoft SEAL uses hardware-based AES in counter mode
+    for pseudo-randomness with key generated using std::random_device. If the 
+    AES-NI instruction set is not available, all randomness is generated from 
+    std::random_device. Most academic users in particular should have little 
+    reason to change this.
+    The BFV scheme cannot perform arbitrary computations on encrypted data. 
+    Instead, each ciphertext has a specific quantity called the `invariant noise 
+    budget' -- or `noise budget' for short -- measured in bits. The noise budget 
+    in a freshly encrypted ciphertext (initial noise budget) is determined by 
+    the encryption parameters. Homomorphic operations consume the noise budget 
+    at a rate also determined by the encryption parameters. In BFV the two basic 
+    operations allowed on encrypted data are additions and multiplications, of 
+    which additions can generally be thought of as being nearly free in terms of 
+    noise budget consumption compared to multiplications. Since noise budget 
+    consumption compounds in sequential multiplications, the most significant 
+    factor in choosing appropriate encryption parameters is the multiplicative 
+    depth of the arithmetic circuit that the user wants to evaluate on encrypted
+    data. Once the noise budget of a ciphertext reaches zero it becomes too 
+    corrupted to be decrypted. Thus, it is essential to choose the parameters to 
+    be large enough to support the desired computation; otherwise the result is 
+    impossible to make sense of even with the secret key.
+    */
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+
+    /*
+    The first parameter we set is the degree of the polynomial modulus. This must
+    be a positive power of 2, representing the degree of a power-of-2 cyclotomic 
+    polynomial; it is not necessary to understand what this means. The polynomial 
+    modulus degree should be thought of mainly affecting the security level of the 
+    scheme: larger degree makes the scheme more secure. Larger degree also makes 
+    ciphertext sizes larger, and consequently all operations slower. Recommended 
+    degrees are 1024, 2048, 4096, 8192, 16384, 32768, but it is also possible to 
+    go beyond this. In this example we use a relatively small polynomial modulus.
+    */
+    ep.set_poly_modulus_degree(2048)?;
+
+    /*
+    Next we set the [ciphertext] coefficient modulus (coeff_modulus). The size 
+    of the coefficient modulus should be thought of as the most significant 
+    factor in determining the noise budget in a freshly encrypted ciphertext: 
+    bigger means more noise budget, which is desirable. On the other hand, 
+    a larger coefficient modulus lowers the security level of the scheme. Thus, 
+    if a large noise budget is required for complicated computations, a large 
+    coefficient modulus needs to be used, and the reduction in the security 
+    level must be countered by simultaneously increasing the polynomial modulus. 
+    Overall, this will result in worse performance.
+    
+    To make parameter selection easier for the user, we have constructed sets 
+    of largest safe coefficient moduli for 128-bit and 192-bit security levels
+    for different choices of the polynomial modulus. These default parameters 
+    follow the recommendations in the Security Standard Draft available at 
+    http://HomomorphicEncryption.org. The security estimates are a complicated
+    topic and we highly recommend consulting with experts in the field when 
+    selecting parameters. 
+    Our recommended values for the coefficient modulus can be easily accessed 
+    through the functions 
         
-            (1x^4 + 1x^3 + 1x^1)^2 = 1x^8 + 2x^7 + 1x^6 + 2x^5 + 2x^4 + 1x^2; 
-        
-        further computations will quickly increase the coefficients much more. 
-        Decoding will still work correctly in this case (evaluating the polynomial 
-        at x=2), but since the coefficients of plaintext polynomials are really 
-        integers modulo plain_modulus, implicit reduction modulo plain_modulus may 
-        yield unexpected results. For example, adding 1x^4 + 1x^3 + 1x^1 to itself 
-        plain_modulus many times will result in the constant polynomial 0, which is 
-        clearly not equal to 26 * plain_modulus. It can be difficult to predict when 
-        such overflow will take place especially when computing several sequential
-        multiplications. BatchEncoder (discussed later) makes it easier to predict 
-        encoding overflow conditions but has a stronger restriction on the size of 
-        the numbers it can encode. 
-        The IntegerEncoder is easy to understand and use for simple computations, 
-        and can be a good starting point to learning Microsoft SEAL. However, 
-        advanced users will probably prefer more efficient approaches, such as the 
-        BatchEncoder or the CKKSEncoder (discussed later).
-        [BatchEncoder]
-        If plain_modulus is a prime congruent to 1 modulo 2*poly_modulus_degree, the 
-        plaintext elements can be viewed as 2-by-(poly_modulus_degree / 2) matrices
-        with elements integers modulo plain_modulus. When a desired computation can 
-        be vectorized, using BatchEncoder can result in a massive performance boost
-        over naively encrypting and operating on each input number separately. Thus, 
-        in more complicated computations this is likely to be by far the most 
-        important and useful encoder. In example_bfv_basics_iii() we show how to
-        operate on encrypted matrix plaintexts.
-        In this example we use the IntegerEncoder due to its simplicity. 
-        */
-        let mut ie = bindings_IntegerEncoder_Create(ctx);
-
-        /*
-        We are now ready to generate the secret and public keys. For this purpose 
-        we need an instance of the KeyGenerator class. Constructing a KeyGenerator 
-        automatically generates the public and secret key, which can then be read to 
-        local variables.
-        */
-        let mut kg = bindings_KeyGenerator_Create(ctx);
-        let mut pk = bindings_KeyGenerator_public_key(kg);
-        let mut sk = bindings_KeyGenerator_secret_key(kg);
-
-        /*
-        To be able to encrypt we need to construct an instance of Encryptor. Note 
-        that the Encryptor only requires the public key, as expected.
-        */
-        let mut enc = bindings_Encryptor_Create(ctx, pk);
-
-        /*
-        Computations on the ciphertexts are performed with the Evaluator class. In
-        a real use-case the Evaluator would not be constructed by the same party 
-        that holds the secret key.
-        */
-        let mut ev = bindings_Evaluator_Create(ctx);
-
-        /*
-        We will of course want to decrypt our results to verify that everything worked,
-        so we need to also construct an instance of Decryptor. Note that the Decryptor
-        requires the secret key.
-        */
-        let mut dec = bindings_Decryptor_Create(ctx, sk);
-
-        /*
-        We start by encoding two integers as plaintext polynomials.
-        */
-        let mut x: libc::c_int = 5;
-        let mut p1 = bindings_IntegerEncoder_encode(ie, x);
-
-        let mut y: libc::c_int = -7;
-        let mut p2 = bindings_IntegerEncoder_encode(ie, y);
-
-        /*
-        Encrypting the encoded values is easy.
-        */
-        let mut ct1 = bindings_Encryptor_encrypt(enc, p1);
-        let mut ct2 = bindings_Encryptor_encrypt(enc, p2);
-
-        /*
-        To illustrate the concept of noise budget, we print the budgets in the fresh 
-        encryptions.
-        */
-        println!("Noise budget in ct1: {} bits",
-                 bindings_Decryptor_invariant_noise_budget(dec, ct1));
-        println!("Noise budget in ct2: {} bits",
-                 bindings_Decryptor_invariant_noise_budget(dec, ct2));
-
-        /*
-        As a simple example, we compute (-encrypted1 + encrypted2) * encrypted2. Most 
-        basic arithmetic operations come as in-place two-argument versions that
-        overwrite the first argument with the result, and as three-argument versions
-        taking as separate destination parameter. In most cases the in-place variants
-        are slightly faster.
-        */
-
-        /*
-        Negation is a unary operation and does not consume any noise budget.
-        */
-        bindings_Evaluator_negate_inplace(ev, ct1);
-        println!("Noise budget in -ct1: {} bits",
-                 bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        /*
-        Compute the sum of ct1 and ct2; the sum overwrites ct1.
-        */
-        bindings_Evaluator_add_inplace(ev, ct1, ct2);
-
-        /*
-        Addition sets the noise budget to the minimum of the input noise budgets. 
-        In this case both inputs had roughly the same budget going in, so the output 
-        (in encrypted1) has just a slightly lower budget. Depending on probabilistic 
-        effects the noise growth consumption may or may not be visible when measured 
-        in whole bits.
-        */
-        println!("Noise bugdget in -ct1 + ct2: {} bits",
-                 bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        /*
-        Finally multiply with encrypted2. Again, we use the in-place version of the
-        function, overwriting encrypted1 with the product.
-        */
-        bindings_Evaluator_multiply_inplace(ev, ct1, ct2);
-
-        /*
-        Multiplication consumes a lot of noise budget. This is clearly seen in the
-        print-out. The user can change the plain_modulus to see its effect on the
-        rate of noise budget consumption.
-        */
-        println!("Noise budget in (-ct1 + ct2) * ct2: {} bits",
-                 bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        /*
-        Now we decrypt and decode our result.
-        */
-        let mut p3 = bindings_Decryptor_decrypt(dec, ct1);
-
-        /*
-        Decode to obtain an integer result.
-        */
-        println!("Decoded integer: {}", bindings_IntegerEncoder_decode_int32(ie, p3));
+        DefaultParams::coeff_modulus_128(int)
+        DefaultParams::coeff_modulus_192(int)
+        DefaultParams::coeff_modulus_256(int)
+    for 128-bit, 192-bit, and 256-bit security levels. The integer parameter is 
+    the degree of the polynomial modulus used.
+    
+    In Microsoft SEAL the coefficient modulus is a positive composite number -- 
+    a product of distinct primes of size up to 60 bits. When we talk about the size 
+    of the coefficient modulus we mean the bit length of the product of the primes. 
+    The small primes are represented by instances of the SmallModulus class so for
+    example DefaultParams::coeff_modulus_128(int) returns a vector of SmallModulus 
+    instances. 
+    
+    It is possible for the user to select their own small primes. Since Microsoft 
+    SEAL uses the Number Theoretic Transform (NTT) for polynomial multiplications 
+    modulo the factors of the coefficient modulus, the factors need to be prime 
+    numbers congruent to 1 modulo 2*poly_modulus_degree. We have generated a list 
+    of such prime numbers of various sizes that the user can easily access through 
+    the functions 
+    
+        DefaultParams::small_mods_60bit(int)
+        DefaultParams::small_mods_50bit(int)
+        DefaultParams::small_mods_40bit(int)
+        DefaultParams::small_mods_30bit(int)
+    
+    each of which gives access to an array of primes of the denoted size. These 
+    primes are located in the source file util/globals.cpp. Again, please keep 
+    in mind that the choice of coeff_modulus has a dramatic effect on security 
+    and should almost always be obtained through coeff_modulus_xxx(int).
+    Performance is mainly affected by the size of the polynomial modulus, and 
+    the number of prime factors in the coefficient modulus; hence in some cases
+    it can be important to use as few prime factors in the coefficient modulus 
+    as possible.
+    In this example we use the default coefficient modulus for a 128-bit security
+    level. Concretely, this coefficient modulus consists of only one 54-bit prime 
+    factor: 0x3fffffff000001.
+    */
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 2048)?;
+
+    /*
+    The plaintext modulus can be any positive integer, even though here we take 
+    it to be a power of two. In fact, in many cases one might instead want it 
+    to be a prime number; we will see this in later examples. The plaintext 
+    modulus determines the size of the plaintext data type but it also affects 
+    the noise budget in a freshly encrypted ciphertext and the consumption of
+    noise budget in homomorphic (encrypted) multiplications. Thus, it is 
+    essential to try to keep the plaintext data type as small as possible for 
+    best performance. The noise budget in a freshly encrypted ciphertext is 
+    
+        ~ log2(coeff_modulus/plain_modulus) (bits)
+    and the noise budget consumption in a homomorphic multiplication is of the 
+    form log2(plain_modulus) + (other terms).
+    */
+    ep.set_plain_modulus_u64(256)?;
+
+    /*
+    Now that all parameters are set, we are ready to construct a SEALContext 
+    object. This is a heavy class that checks the validity and properties of the 
+    parameters we just set and performs several important pre-computations.
+    */
+    let ctx = Context::new(&ep, false)?;
+
+    /*
+    Plaintexts in the BFV scheme are polynomials with coefficients integers 
+    modulo plain_modulus. This is not a very practical object to encrypt: much
+    more useful would be encrypting integers or floating point numbers. For this
+    we need an `encoding scheme' to convert data from integer representation to
+    an appropriate plaintext polynomial representation than can subsequently be 
+    encrypted. Microsoft SEAL comes with a few basic encoders for the BFV scheme:
+    [IntegerEncoder]
+    The IntegerEncoder encodes integers to plaintext polynomials as follows. 
+    First, a binary expansion of the integer is computed. Next, a polynomial is
+    created with the bits as coefficients. For example, the integer 
+    
+        26 = 2^4 + 2^3 + 2^1
+    
+    is encoded as the polynomial 1x^4 + 1x^3 + 1x^1. Conversely, plaintext
+    polynomials are decoded by evaluating them at x=2. For negative numbers the
+    IntegerEncoder simply stores all coefficients as either 0 or -1, where -1 is
+    represented by the unsigned integer plain_modulus - 1 in memory.
+    Since encrypted computations operate on the polynomials rather than on the
+    encoded integers themselves, the polynomial coefficients will grow in the
+    course of such computations. For example, computing the sum of the encrypted
+    encoded integer 26 with itself will result in an encrypted polynomial with
+    larger coefficients: 2x^4 + 2x^3 + 2x^1. Squaring the encrypted encoded
+    integer 26 results also in increased coefficients due to cross-terms, namely,
+    
+        (1x^4 + 1x^3 + 1x^1)^2 = 1x^8 + 2x^7 + 1x^6 + 2x^5 + 2x^4 + 1x^2; 
+    
+    further computations will quickly increase the coefficients much more. 
+    Decoding will still work correctly in this case (evaluating the polynomial 
+    at x=2), but since the coefficients of plaintext polynomials are really 
+    integers modulo plain_modulus, implicit reduction modulo plain_modulus may 
+    yield unexpected results. For example, adding 1x^4 + 1x^3 + 1x^1 to itself 
+    plain_modulus many times will result in the constant polynomial 0, which is 
+    clearly not equal to 26 * plain_modulus. It can be difficult to predict when 
+    such overflow will take place especially when computing several sequential
+    multiplications. BatchEncoder (discussed later) makes it easier to predict 
+    encoding overflow conditions but has a stronger restriction on the size of 
+    the numbers it can encode. 
+    The IntegerEncoder is easy to understand and use for simple computations, 
+    and can be a good starting point to learning Microsoft SEAL. However, 
+    advanced users will probably prefer more efficient approaches, such as the 
+    BatchEncoder or the CKKSEncoder (discussed later).
+    [BatchEncoder]
+    If plain_modulus is a prime congruent to 1 modulo 2*poly_modulus_degree, the 
+    plaintext elements can be viewed as 2-by-(poly_modulus_degree / 2) matrices
+    with elements integers modulo plain_modulus. When a desired computation can 
+    be vectorized, using BatchEncoder can result in a massive performance boost
+    over naively encrypting and operating on each input number separately. Thus, 
+    in more complicated computations this is likely to be by far the most 
+    important and useful encoder. In example_bfv_basics_iii() we show how to
+    operate on encrypted matrix plaintexts.
+    In this example we use the IntegerEncoder due to its simplicity. 
+    */
+    let ie = IntegerEncoder::new(&ctx)?;
+
+    /*
+    We are now ready to generate the secret and public keys. For this purpose 
+    we need an instance of the KeyGenerator class. Constructing a KeyGenerator 
+    automatically generates the public and secret key, which can then be read to 
+    local variables.
+    */
+    let kg = KeyGenerator::new(&ctx)?;
+    let pk = kg.public_key()?;
+    let sk = kg.secret_key()?;
+
+    /*
+    To be able to encrypt we need to construct an instance of Encryptor. Note 
+    that the Encryptor only requires the public key, as expected.
+    */
+    let enc = Encryptor::new(&ctx, &pk)?;
+
+    /*
+    Computations on the ciphertexts are performed with the Evaluator class. In
+    a real use-case the Evaluator would not be constructed by the same party 
+    that holds the secret key.
+    */
+    let ev = Evaluator::new(&ctx)?;
+
+    /*
+    We will of course want to decrypt our results to verify that everything worked,
+    so we need to also construct an instance of Decryptor. Note that the Decryptor
+    requires the secret key.
+    */
+    let dec = Decryptor::new(&ctx, &sk)?;
+
+    /*
+    We start by encoding two integers as plaintext polynomials.
+    */
+    let x = 5;
+    let p1 = ie.encode(x)?;
+
+    let y = -7;
+    let p2 = ie.encode(y)?;
+
+    /*
+    Encrypting the encoded values is easy.
+    */
+    let mut ct1 = enc.encrypt(&p1)?;
+    let ct2 = enc.encrypt(&p2)?;
+
+    /*
+    To illustrate the concept of noise budget, we print the budgets in the fresh 
+    encryptions.
+    */
+    println!("Noise budget in ct1: {} bits",
+             dec.invariant_noise_budget(&ct1)?);
+    println!("Noise budget in ct2: {} bits",
+             dec.invariant_noise_budget(&ct2)?);
+
+    /*
+    As a simple example, we compute (-encrypted1 + encrypted2) * encrypted2. Most 
+    basic arithmetic operations come as in-place two-argument versions that
+    overwrite the first argument with the result, and as three-argument versions
+    taking as separate destination parameter. In most cases the in-place variants
+    are slightly faster.
+    */
+
+    /*
+    Negation is a unary operation and does not consume any noise budget.
+    */
+    ev.negate_inplace(&mut ct1)?;
+    println!("Noise budget in -ct1: {} bits",
+             dec.invariant_noise_budget(&ct1)?);
+
+    /*
+    Compute the sum of ct1 and ct2; the sum overwrites ct1.
+    */
+    ev.add_inplace(&mut ct1, &ct2)?;
+
+    /*
+    Addition sets the noise budget to the minimum of the input noise budgets. 
+    In this case both inputs had roughly the same budget going in, so the output 
+    (in encrypted1) has just a slightly lower budget. Depending on probabilistic 
+    effects the noise growth consumption may or may not be visible when measured 
+    in whole bits.
+    */
+    println!("Noise bugdget in -ct1 + ct2: {} bits",
+             dec.invariant_noise_budget(&ct1)?);
+
+    /*
+    Finally multiply with encrypted2. Again, we use the in-place version of the
+    function, overwriting encrypted1 with the product.
+    */
+    ev.multiply_inplace(&mut ct1, &ct2)?;
+
+    /*
+    Multiplication consumes a lot of noise budget. This is clearly seen in the
+    print-out. The user can change the plain_modulus to see its effect on the
+    rate of noise budget consumption.
+    */
+    println!("Noise budget in (-ct1 + ct2) * ct2: {} bits",
+             dec.invariant_noise_budget(&ct1)?);
+
+    /*
+    Now we decrypt and decode our result.
+    */
+    let p3 = dec.decrypt(&ct1)?;
+
+    /*
+    Decode to obtain an integer result.
+    */
+    println!("Decoded integer: {}", ie.decode_i32(&p3)?);
+    Ok(())
+}
+
+#[test]
+fn example_bfv_basics_ii() -> Result<()> {
+    println!("Example: BFV Basics II");
+
+    /*
+    In this example we explain what relinearization is, how to use it, and how 
+    it affects noise budget consumption. Relinearization is used both in the BFV
+    and the CKKS schemes but in this example (for the sake of simplicity) we 
+    again focus on BFV.
+    First we set the parameters, create a SEALContext, and generate the public
+    and secret keys. We use slightly larger parameters than before to be able to 
+    do more homomorphic multiplications.
+    */
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(8192)?;
+
+    /*
+    The default coefficient modulus consists of the following primes:
+        0x7fffffff380001,  0x7ffffffef00001,
+        0x3fffffff000001,  0x3ffffffef40001
+    The total size is 218 bits.
+    */
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 8192)?;
+    ep.set_plain_modulus_u64(1024)?;
+
+    let ctx = Context::new(&ep, false)?;
+
+    /*
+    We generate the public and secret keys as before. 
+    There are actually two more types of keys in Microsoft SEAL: `relinearization keys' 
+    and `Galois keys'. In this example we will discuss relinearization keys, and 
+    Galois keys will be discussed later in example_bfv_basics_iii().
+    */
+    let kg = KeyGenerator::new(&ctx)?;
+    let pk = kg.public_key()?;
+    let sk = kg.secret_key()?;
+
+    /*
+    We also set up an Encryptor, Evaluator, and Decryptor here. We will
+    encrypt polynomials directly in this example, so there is no need for
+    an encoder.
+    */
+    let enc = Encryptor::new(&ctx, &pk)?;
+    let ev = Evaluator::new(&ctx)?;
+    let dec = Decryptor::new(&ctx, &sk)?;
+
+    /*
+    We can easily construct a plaintext polynomial from a string. Again, note 
+    how there is no need for encoding since the BFV scheme natively encrypts
+    polynomials.
+    */
+    let pt1 = Plaintext::from_hex_poly("1x^2 + 2x^1 + 3")?;
+    let mut ct1 = enc.encrypt(&pt1)?;
+
+    /*
+    In Microsoft SEAL, a valid ciphertext consists of two or more polynomials whose 
+    coefficients are integers modulo the product of the primes in coeff_modulus. 
+    The current size of a ciphertext can be found using Ciphertext::size().
+    A freshly encrypted ciphertext always has size 2.
+    */
+    println!("Size of fresh encryption: {}", ct1.size()?);
+    println!("Noise budget in fresh encryption: {} bits", dec.invariant_noise_budget(&ct1)?);
+
+    /*
+    Homomorphic multiplication results in the output ciphertext growing in size. 
+    More precisely, if the input ciphertexts have size M and N, then the output 
+    ciphertext after homomorphic multiplication will have size M+N-1. In this
+    case we square encrypted twice to observe this growth (also observe noise
+    budget consumption).
+    */
+    ev.square_inplace(&mut ct1)?;
+
+    println!("Size after squaring: {}", ct1.size()?);
+    println!("Noise budget after squaring: {}", dec.invariant_noise_budget(&ct1)?);
+
+    ev.square_inplace(&mut ct1)?;
+
+    println!("Size after second squaring: {}", ct1.size()?);
+    println!("Noise budget after second squaring: {}", dec.invariant_noise_budget(&ct1)?);
+
+    /*
+    It does not matter that the size has grown -- decryption works as usual.
+    Observe from the print-out that the coefficients in the plaintext have grown 
+    quite large. One more squaring would cause some of them to wrap around the
+    plain_modulus (0x400) and as a result we would no longer obtain the expected 
+    result as an integer-coefficient polynomial. We can fix this problem to some 
+    extent by increasing plain_modulus. This makes sense since we still have 
+    plenty of noise budget left.
+    */
+    let pt2 = dec.decrypt(&ct1)?;
+
+    println!("Fourth power: {}", pt2);
+
+    /*
+    The problem here is that homomorphic operations on large ciphertexts are
+    computationally much more costly than on small ciphertexts. Specifically,
+    homomorphic multiplication on input ciphertexts of size M and N will require 
+    O(M*N) polynomial multiplications to be performed, and an addition will
+    require O(M+N) additions. Relinearization reduces the size of ciphertexts
+    after multiplication back to the initial size (2). Thus, relinearizing one
+    or both inputs before the next multiplication or e.g. before serializing the
+    ciphertexts, can have a huge positive impact on performance.
+    Another problem is that the noise budget consumption in multiplication is
+    bigger when the input ciphertexts sizes are bigger. In a complicated
+    computation the contribution of the sizes to the noise budget consumption
+    can actually become the dominant term. We will point this out again below
+    once we get to our example.
+    Relinearization itself has both a computational cost and a noise budget cost.
+    These both depend on a parameter called `decomposition bit count', which can
+    be any integer at least 1 [dbc_min()] and at most 60 [dbc_max()]. A large
+    decomposition bit count makes relinearization fast, but consumes more noise
+    budget. A small decomposition bit count can make relinearization slower, but 
+    might not change the noise budget by any observable amount.
+    Relinearization requires a special type of key called `relinearization keys'.
+    These can be created by the KeyGenerator for any decomposition bit count.
+    To relinearize a ciphertext of size M >= 2 back to size 2, we actually need 
+    M-2 relinearization keys. Attempting to relinearize a too large ciphertext 
+    with too few relinearization keys will result in an exception being thrown.
+    We repeat our computation, but this time relinearize after both squarings.
+    Since our ciphertext never grows past size 3 (we relinearize after every
+    multiplication), it suffices to generate only one relinearization key. This
+    (relinearizing after every multiplication) should be the preferred approach 
+    in almost all cases.
+    First, we need to create relinearization keys. We use a decomposition bit 
+    count of 16 here, which should be thought of as very small.
+    This function generates one single relinearization key. Another overload 
+    of KeyGenerator::relin_keys takes the number of keys to be generated as an 
+    argument, but one is all we need in this example (see above).
+    */
+    let rk16 = kg.relin_keys(16, 1)?;
+
+    let mut ct2 = enc.encrypt(&pt1)?;
+
+    println!("Size of fresh encryption: {}", ct2.size()?);
+    println!("Noise budget in fresh encryption: {} bits", dec.invariant_noise_budget(&ct2)?);
+
+    ev.square_inplace(&mut ct2)?;
+
+    println!("Size after squaring: {}", ct2.size()?);
+    println!("Noise budget after squaring: {}", dec.invariant_noise_budget(&ct2)?);
+
+    ev.relinearize_inplace(&mut ct2, &rk16)?;
+
+    println!("Size after relinearization: {}", ct2.size()?);
+    println!("Noise budget after relinearization (dbc = 16): {}", dec.invariant_noise_budget(&ct2)?);
+
+    ev.square_inplace(&mut ct2)?;
+
+    println!("Size after second squaring: {}", ct2.size()?);
+    println!("Noise budget after second squaring: {}", dec.invariant_noise_budget(&ct2)?);
+
+    ev.relinearize_inplace(&mut ct2, &rk16)?;
+    
+    println!("Size after relinearization: {}", ct2.size()?);
+    println!("Noise budget after relinearization (dbc = 16): {}", dec.invariant_noise_budget(&ct2)?);
+
+    let pt3 = dec.decrypt(&ct2)?;
+
+    println!("Fourth power: {}", pt3);
+
+    /*
+    Of course the result is still the same, but this time we actually used less 
+    of our noise budget. This is not surprising for two reasons:
+    
+        - We used a very small decomposition bit count, which is why
+        relinearization itself did not consume the noise budget by any
+        observable amount;
+        - Since our ciphertext sizes remain small throughout the two
+        squarings, the noise budget consumption rate in multiplication
+        remains as small as possible. Recall from above that operations
+        on larger ciphertexts actually cause more noise growth.
+    To make things more clear, we repeat the computation a third time, now using 
+    the largest possible decomposition bit count (60). We are not measuring
+    running time here, but relinearization with relin_keys60 (below) is much 
+    faster than with relin_keys16.
+    */
+    let rk60 = kg.relin_keys(60, 1)?;
+
+    let mut ct3 = enc.encrypt(&pt1)?;
+
+    println!("Size of fresh encryption: {}", ct3.size()?);
+    println!("Noise budget in fresh encryption: {} bits", dec.invariant_noise_budget(&ct3)?);
+
+    ev.square_inplace(&mut ct3)?;
+
+    println!("Size after squaring: {}", ct3.size()?);
+    println!("Noise budget after squaring: {}", dec.invariant_noise_budget(&ct3)?);
+
+    ev.relinearize_inplace(&mut ct3, &rk60)?;
+
+    println!("Size after relinearization: {}", ct3.size()?);
+    println!("Noise budget after relinearization (dbc = 60): {}", dec.invariant_noise_budget(&ct3)?);
+
+    ev.square_inplace(&mut ct3)?;
+
+    println!("Size after second squaring: {}", ct3.size()?);
+    println!("Noise budget after second squaring: {}", dec.invariant_noise_budget(&ct3)?);
+
+    ev.relinearize_inplace(&mut ct3, &rk60)?;
+    
+    println!("Size after relinearization: {}", ct3.size()?);
+    println!("Noise budget after relinearization (dbc = 60): {}", dec.invariant_noise_budget(&ct3)?);
+
+    let pt3 = dec.decrypt(&ct3)?;
+
+    println!("Fourth power: {}", pt3);
+
+    /*
+    Observe from the print-out that we have now used significantly more of our
+    noise budget than in the two previous runs. This is again not surprising, 
+    since the first relinearization chops off a huge part of the noise budget.
+
+    However, note that the second relinearization does not change the noise
+    budget by any observable amount. This is very important to understand when
+    optimal performance is desired: relinearization always drops the noise
+    budget from the maximum (freshly encrypted ciphertext) down to a fixed 
+    amount depending on the encryption parameters and the decomposition bit 
+    count. On the other hand, homomorphic multiplication always consumes the
+    noise budget from its current level. This is why the second relinearization
+    does not change the noise budget anymore: it is already consumed past the
+    fixed amount determinted by the decomposition bit count and the encryption
+    parameters. 
+
+    We now perform a third squaring and observe an even further compounded
+    decrease in the noise budget. Again, relinearization does not consume the
+    noise budget at this point by any observable amount, even with the largest
+    possible decomposition bit count.
+    */
+
+    ev.square_inplace(&mut ct3)?;
+
+    println!("Size after third squaring: {}", ct3.size()?);
+    println!("Noise budget after third squaring: {}", dec.invariant_noise_budget(&ct3)?);
+
+    ev.relinearize_inplace(&mut ct3, &rk60)?;
+    
+    println!("Size after relinearization: {}", ct3.size()?);
+    println!("Noise budget after relinearization (dbc = 60): {}", dec.invariant_noise_budget(&ct3)?);
+
+    let pt3 = dec.decrypt(&ct3)?;
+
+    println!("Eighth power: {}", pt3);
+
+    /*
+    Observe from the print-out that the polynomial coefficients are no longer
+    correct as integers: they have been reduced modulo plain_modulus, and there
+    was no warning sign about this. It might be necessary to carefully analyze
+    the computation to make sure such overflow does not occur unexpectedly.
+    These experiments suggest that an optimal strategy might be to relinearize
+    first with relinearization keys with a small decomposition bit count, and 
+    later with relinearization keys with a larger decomposition bit count (for 
+    performance) when noise budget has already been consumed past the bound 
+    determined by the larger decomposition bit count. For example, the best 
+    strategy might have been to use relin_keys16 in the first relinearization 
+    and relin_keys60 in the next two relinearizations for optimal noise budget 
+    consumption/performance trade-off. Luckily, in most use-cases it is not so 
+    critical to squeeze out every last bit of performance, especially when 
+    larger parameters are used.
+    */
+
+    Ok(())
+}
+#[test]
+fn example_bfv_basics_iii() -> Result<()> {
+    println!("Example: BFV Basics III");
+
+    /*
+    In this fundamental example we discuss and demonstrate a powerful technique 
+    called `batching'. If N denotes the degree of the polynomial modulus, and T
+    the plaintext modulus, then batching is automatically enabled for the BFV
+    scheme when T is a prime number congruent to 1 modulo 2*N. In batching the 
+    plaintexts are viewed as matrices of size 2-by-(N/2) with each element an 
+    integer modulo T. Homomorphic operations act element-wise between encrypted 
+    matrices, allowing the user to obtain speeds-ups of several orders of 
+    magnitude in naively vectorizable computations. We demonstrate two more 
+    homomorphic operations which act on encrypted matrices by rotating the rows 
+    cyclically, or rotate the columns (i.e. swap the rows). These operations 
+    require the construction of so-called `Galois keys', which are very similar 
+    to relinearization keys.
+    The batching functionality is totally optional in the BFV scheme and is 
+    exposed through the BatchEncoder class. 
+    */
+
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    
+    ep.set_poly_modulus_degree(4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 4096)?;
+
+    /*
+    Note that 40961 is a prime number and 2*4096 divides 40960, so batching will
+    automatically be enabled for these parameters.
+    */
+    ep.set_plain_modulus_u64(40961)?;
+
+    let ctx = Context::new(&ep, false)?;
+
+    /*
+    We can verify that batching is indeed enabled by looking at the encryption
+    parameter qualifiers created by SEALContext.
+    */
+    let qualifiers = ctx.qualifiers()?;
+    println!("Batching enabled: {}", qualifiers.using_batching);
+    assert!(qualifiers.using_batching);
+
+    let kg = KeyGenerator::new(&ctx)?;
+    let pk = kg.public_key()?;
+    let sk = kg.secret_key()?;
+
+    /*
+    We need to create so-called `Galois keys' for performing matrix row and 
+    column rotations on encrypted matrices. Like relinearization keys, the 
+    behavior of Galois keys depends on a decomposition bit count. The noise 
+    budget consumption behavior of matrix row and column rotations is exactly 
+    like that of relinearization (recall example_bfv_basics_ii()).
+    Here we use a moderate size decomposition bit count.
+    */
+    let gal_keys = kg.galois_keys(30)?;
+
+    /*
+    Since we are going to do some multiplications we will also relinearize.
+    */
+    let relin_keys = kg.relin_keys(30, 1)?;
+
+    /*
+    We also set up an Encryptor, Evaluator, and Decryptor here.
+    */
+    let enc = Encryptor::new(&ctx, &pk)?;
+    let ev = Evaluator::new(&ctx)?;
+    let dec = Decryptor::new(&ctx, &sk)?;
+
+    /*
+    Batching is done through an instance of the BatchEncoder class so need to
+    construct one.
+    */
+    let be = BatchEncoder::new(&ctx)?;
+
+    /*
+    The total number of batching `slots' is poly_modulus_degree. The matrices 
+    we encrypt are of size 2-by-(slot_count / 2).
+    */
+    let slot_count = be.slot_count();
+    let row_size = slot_count / 2;
+    println!("Plaintext matrix row size: {}", row_size);
+
+    /*
+    Printing the matrix is a bit of a pain.
+    ... so I won't do it
+    */
+
+    /*
+    The matrix plaintext is simply given to BatchEncoder as a flattened vector
+    of numbers of size slot_count. The first row_size numbers form the first row, 
+    and the rest form the second row. Here we create the following matrix:
+        [ 0,  1,  2,  3,  0,  0, ...,  0 ]
+        [ 4,  5,  6,  7,  0,  0, ...,  0 ]
+    */
+    let mut pod_matrix = vec![0u64; slot_count];
+    pod_matrix[0] = 0;
+    pod_matrix[1] = 1;
+    pod_matrix[2] = 2;
+    pod_matrix[3] = 3;
+    pod_matrix[row_size] = 4;
+    pod_matrix[row_size + 1] = 5;
+    pod_matrix[row_size + 2] = 6;
+    pod_matrix[row_size + 3] = 7;
+
+    /*
+    First we use BatchEncoder to compose the matrix into a plaintext.
+    */
+    let plain_matrix = be.encode(&pod_matrix)?;
+
+    /*
+    Next we encrypt the plaintext as usual.
+    */
+    let mut encrypted_matrix = enc.encrypt(&plain_matrix)?;
+    println!("Noise budget in fresh encryption: {} bits", dec.invariant_noise_budget(&encrypted_matrix)?);
+
+    /*
+    Operating on the ciphertext results in homomorphic operations being performed
+    simultaneously in all 4096 slots (matrix elements). To illustrate this, we 
+    form another plaintext matrix
+        [ 1,  2,  1,  2,  1,  2, ..., 2 ]
+        [ 1,  2,  1,  2,  1,  2, ..., 2 ]
+    and compose it into a plaintext.
+    */
+    let pod_matrix2: Vec<u64> = (0..slot_count).map(|i| (i % 2) as u64 + 1).collect();
+    let plain_matrix2 = be.encode(&pod_matrix2)?;
+
+    /*
+    We now add the second (plaintext) matrix to the encrypted one using another 
+    new operation -- plain addition -- and square the sum.
+    */
+    ev.add_plain_inplace(&mut encrypted_matrix, &plain_matrix2)?;
+    ev.square_inplace(&mut encrypted_matrix)?;
+    ev.relinearize_inplace(&mut encrypted_matrix, &relin_keys)?;
+
+    /*
+    How much noise budget do we have left?
+    */
+    println!("Noise budget in result: {} bits", dec.invariant_noise_budget(&encrypted_matrix)?);
+
+    /*
+    We decrypt and decompose the plaintext to recover the result as a matrix.
+    */
+    let result = be.decode(&dec.decrypt(&encrypted_matrix)?)?;
+    let expected: Vec<u64> = (0..slot_count)
+        .map(|i| (pod_matrix[i] + pod_matrix2[i]) * (pod_matrix[i] + pod_matrix2[i]))
+        .collect();
+    assert_eq!(result, expected);
+
+    /*
+    Note that we have done only one encryption and one decryption, yet the whole
+    computation was done on all 4096 slots at once.
+    */
+
+    /*
+    Now we rotate the rows of the matrix 3 steps to the left, which moves the
+    first row from [ 1, 9, 9, 25, 1, 4, ... ] to [ 25, 1, 4, 1, 4, ... ]. Like
+    relinearization, rotations consume noise budget according to the
+    decomposition bit count of the Galois keys.
+    */
+    ev.rotate_rows_inplace(&mut encrypted_matrix, 3, &gal_keys)?;
+    println!("Noise budget after rotating rows: {} bits", dec.invariant_noise_budget(&encrypted_matrix)?);
+    let rotated = be.decode_matrix(&dec.decrypt(&encrypted_matrix)?)?;
+    for column in 0..row_size {
+        let source = (column + 3) % row_size;
+        assert_eq!(rotated.column(column), [expected[source], expected[row_size + source]]);
+    }
+
+    /*
+    Rotating the columns swaps the two rows.
+    */
+    ev.rotate_columns_inplace(&mut encrypted_matrix, &gal_keys)?;
+    println!("Noise budget after rotating columns: {} bits", dec.invariant_noise_budget(&encrypted_matrix)?);
+    let swapped = be.decode_matrix(&dec.decrypt(&encrypted_matrix)?)?;
+    assert_eq!(swapped.row(0), rotated.row(1));
+    assert_eq!(swapped.row(1), rotated.row(0));
+
+    /*
+    Finally we rotate the rows 4 steps to the right, rotate the columns back
+    and check that we recover the result with the rows shifted 1 step right.
+    */
+    ev.rotate_rows_inplace(&mut encrypted_matrix, -4, &gal_keys)?;
+    ev.rotate_columns_inplace(&mut encrypted_matrix, &gal_keys)?;
+    println!("Noise budget after rotating back: {} bits", dec.invariant_noise_budget(&encrypted_matrix)?);
+    let result = be.decode_matrix(&dec.decrypt(&encrypted_matrix)?)?;
+    for column in 0..row_size {
+        let source = (column + row_size - 1) % row_size;
+        assert_eq!(result.column(column), [expected[source], expected[row_size + source]]);
     }
+
+    Ok(())
 }
 
 #[test]
-fn example_bfv_basics_ii() {
-    unsafe {
-        println!("Example: BFV Basics II");
-
-        /*
-        In this example we explain what relinearization is, how to use it, and how 
-        it affects noise budget consumption. Relinearization is used both in the BFV
-        and the CKKS schemes but in this example (for the sake of simplicity) we 
-        again focus on BFV.
-        First we set the parameters, create a SEALContext, and generate the public
-        and secret keys. We use slightly larger parameters than before to be able to 
-        do more homomorphic multiplications.
-        */
-        let mut ep = bindings_EncryptionParameters_Create(1);
-        bindings_EncryptionParameters_set_poly_modulus_degree(ep, 8192);
-
-        /*
-        The default coefficient modulus consists of the following primes:
-            0x7fffffff380001,  0x7ffffffef00001,
-            0x3fffffff000001,  0x3ffffffef40001
-        The total size is 218 bits.
-        */
-        bindings_EncryptionParameters_set_coeff_modulus(ep, 128, 8192);
-        bindings_EncryptionParameters_set_plain_modulus(ep, 1024);
-
-        let mut ctx = bindings_SEALContext_Create(ep, false);
-
-        /*
-        We generate the public and secret keys as before. 
-        There are actually two more types of keys in Microsoft SEAL: `relinearization keys' 
-        and `Galois keys'. In this example we will discuss relinearization keys, and 
-        Galois keys will be discussed later in example_bfv_basics_iii().
-        */
-        let mut kg = bindings_KeyGenerator_Create(ctx);
-        let mut pk = bindings_KeyGenerator_public_key(kg);
-        let mut sk = bindings_KeyGenerator_secret_key(kg);
-
-        /*
-        We also set up an Encryptor, Evaluator, and Decryptor here. We will
-        encrypt polynomials directly in this example, so there is no need for
-        an encoder.
-        */
-        let mut enc = bindings_Encryptor_Create(ctx, pk);
-        let mut ev = bindings_Evaluator_Create(ctx);
-        let mut dec = bindings_Decryptor_Create(ctx, sk);
-
-        /*
-        We can easily construct a plaintext polynomial from a string. Again, note 
-        how there is no need for encoding since the BFV scheme natively encrypts
-        polynomials.
-        */
-        let mut pt1 = bindings_Plaintext_Create(CString::new("1x^2 + 2x^1 + 3").expect("Something happened").as_ptr());
-        let mut ct1 = bindings_Encryptor_encrypt(enc, pt1);
-
-        /*
-        In Microsoft SEAL, a valid ciphertext consists of two or more polynomials whose 
-        coefficients are integers modulo the product of the primes in coeff_modulus. 
-        The current size of a ciphertext can be found using Ciphertext::size().
-        A freshly encrypted ciphertext always has size 2.
-        */
-        println!("Size of fresh encryption: {}", bindings_Ciphertext_size(ct1));
-        println!("Noise budget in fresh encryption: {} bits", bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        /*
-        Homomorphic multiplication results in the output ciphertext growing in size. 
-        More precisely, if the input ciphertexts have size M and N, then the output 
-        ciphertext after homomorphic multiplication will have size M+N-1. In this
-        case we square encrypted twice to observe this growth (also observe noise
-        budget consumption).
-        */
-        bindings_Evaluator_square_inplace(ev, ct1);
-
-        println!("Size after squaring: {}", bindings_Ciphertext_size(ct1));
-        println!("Noise budget after squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        bindings_Evaluator_square_inplace(ev, ct1);
-
-        println!("Size after second squaring: {}", bindings_Ciphertext_size(ct1));
-        println!("Noise budget after second squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct1));
-
-        /*
-        It does not matter that the size has grown -- decryption works as usual.
-        Observe from the print-out that the coefficients in the plaintext have grown 
-        quite large. One more squaring would cause some of them to wrap around the
-        plain_modulus (0x400) and as a result we would no longer obtain the expected 
-        result as an integer-coefficient polynomial. We can fix this problem to some 
-        extent by increasing plain_modulus. This makes sense since we still have 
-        plenty of noise budget left.
-        */
-        let mut pt2 = bindings_Decryptor_decrypt(dec, ct1);
-
-        println!("Fourth power: {}", CStr::from_ptr(bindings_Plaintext_to_string(pt2)).to_str().unwrap());
-
-        /*
-        The problem here is that homomorphic operations on large ciphertexts are
-        computationally much more costly than on small ciphertexts. Specifically,
-        homomorphic multiplication on input ciphertexts of size M and N will require 
-        O(M*N) polynomial multiplications to be performed, and an addition will
-        require O(M+N) additions. Relinearization reduces the size of ciphertexts
-        after multiplication back to the initial size (2). Thus, relinearizing one
-        or both inputs before the next multiplication or e.g. before serializing the
-        ciphertexts, can have a huge positive impact on performance.
-        Another problem is that the noise budget consumption in multiplication is
-        bigger when the input ciphertexts sizes are bigger. In a complicated
-        computation the contribution of the sizes to the noise budget consumption
-        can actually become the dominant term. We will point this out again below
-        once we get to our example.
-        Relinearization itself has both a computational cost and a noise budget cost.
-        These both depend on a parameter called `decomposition bit count', which can
-        be any integer at least 1 [dbc_min()] and at most 60 [dbc_max()]. A large
-        decomposition bit count makes relinearization fast, but consumes more noise
-        budget. A small decomposition bit count can make relinearization slower, but 
-        might not change the noise budget by any observable amount.
-        Relinearization requires a special type of key called `relinearization keys'.
-        These can be created by the KeyGenerator for any decomposition bit count.
-        To relinearize a ciphertext of size M >= 2 back to size 2, we actually need 
-        M-2 relinearization keys. Attempting to relinearize a too large ciphertext 
-        with too few relinearization keys will result in an exception being thrown.
-        We repeat our computation, but this time relinearize after both squarings.
-        Since our ciphertext never grows past size 3 (we relinearize after every
-        multiplication), it suffices to generate only one relinearization key. This
-        (relinearizing after every multiplication) should be the preferred approach 
-        in almost all cases.
-        First, we need to create relinearization keys. We use a decomposition bit 
-        count of 16 here, which should be thought of as very small.
-        This function generates one single relinearization key. Another overload 
-        of KeyGenerator::relin_keys takes the number of keys to be generated as an 
-        argument, but one is all we need in this example (see above).
-        */
-        let mut rk16 = bindings_KeyGenerator_relin_keys(kg, 16, 1);
-
-        let mut ct2 = bindings_Encryptor_encrypt(enc, pt1);
-
-        println!("Size of fresh encryption: {}", bindings_Ciphertext_size(ct2));
-        println!("Noise budget in fresh encryption: {} bits", bindings_Decryptor_invariant_noise_budget(dec, ct2));
-
-        bindings_Evaluator_square_inplace(ev, ct2);
-
-        println!("Size after squaring: {}", bindings_Ciphertext_size(ct2));
-        println!("Noise budget after squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct2));
-
-        bindings_Evaluator_relinearize_inplace(ev, ct2, rk16);
-
-        println!("Size after relinearization: {}", bindings_Ciphertext_size(ct2));
-        println!("Noise budget after relinearization (dbc = 16): {}", bindings_Decryptor_invariant_noise_budget(dec, ct2));
-
-        bindings_Evaluator_square_inplace(ev, ct2);
-
-        println!("Size after second squaring: {}", bindings_Ciphertext_size(ct2));
-        println!("Noise budget after second squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct2));
-
-        bindings_Evaluator_relinearize_inplace(ev, ct2, rk16);
-        
-        println!("Size after relinearization: {}", bindings_Ciphertext_size(ct2));
-        println!("Noise budget after relinearization (dbc = 16): {}", bindings_Decryptor_invariant_noise_budget(dec, ct2));
+fn context_outlives_its_last_clone() -> Result<()> {
+    let (ie, enc, dec) = {
+        let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+        ep.set_poly_modulus_degree(2048)?;
+        ep.set_coeff_modulus(SecurityLevel::Tc128, 2048)?;
+        ep.set_plain_modulus_u64(256)?;
+
+        let ctx = Context::new(&ep, false)?;
+        let kg = KeyGenerator::new(&ctx)?;
+        let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+        let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+        (IntegerEncoder::new(&ctx)?, enc, dec)
+    };
+
+    let ct = enc.encrypt(&ie.encode(12)?)?;
+    assert_eq!(ie.decode_i32(&dec.decrypt(&ct)?)?, 12);
+    Ok(())
+}
 
-        let mut pt3 = bindings_Decryptor_decrypt(dec, ct2);
+#[test]
+fn seal_exceptions_become_errors() -> Result<()> {
+    match Plaintext::from_hex_poly("not a polynomial") {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        println!("Fourth power: {}", CStr::from_ptr(bindings_Plaintext_to_string(pt3)).to_str().unwrap());
+    // No plain modulus: the context is created but its parameters are not
+    // valid, which SEAL only reports once keys are generated.
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(2048)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 2048)?;
+    let ctx = Context::new(&ep, false)?;
+    match KeyGenerator::new(&ctx) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
 
-        /*
-        Of course the result is still the same, but this time we actually used less 
-        of our noise budget. This is not surprising for two reasons:
-        
-            - We used a very small decomposition bit count, which is why
-            relinearization itself did not consume the noise budget by any
-            observable amount;
-            - Since our ciphertext sizes remain small throughout the two
-            squarings, the noise budget consumption rate in multiplication
-            remains as small as possible. Recall from above that operations
-            on larger ciphertexts actually cause more noise growth.
-        To make things more clear, we repeat the computation a third time, now using 
-        the largest possible decomposition bit count (60). We are not measuring
-        running time here, but relinearization with relin_keys60 (below) is much 
-        faster than with relin_keys16.
-        */
-        let mut rk60 = bindings_KeyGenerator_relin_keys(kg, 60, 1);
+#[test]
+fn default_coeff_modulus_per_security_level() -> Result<()> {
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc192, 4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc256, 4096)?;
+
+    match ep.set_coeff_modulus(SecurityLevel::Tc192, 3000) {
+        Err(Error::OutOfRange(_)) => {}
+        other => panic!("expected OutOfRange, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+#[test]
+fn custom_coeff_modulus_from_default_primes() -> Result<()> {
+    let mut ep = EncryptionParameters::new(SchemeType::Ckks)?;
+    ep.set_poly_modulus_degree(8192)?;
+
+    let mut primes = SmallModulus::default_primes(60, 1)?;
+    primes.extend(SmallModulus::default_primes(40, 3)?);
+    assert!(primes.iter().all(|p| p.value() % (2 * 8192) == 1));
+    assert_eq!(primes[1].bit_count(), 40);
+    ep.set_coeff_modulus_primes(&primes)?;
+
+    // 2^40 + 3 is not congruent to 1 modulo 2 * 8192.
+    let bad = SmallModulus::new((1 << 40) + 3)?;
+    match ep.set_coeff_modulus_primes(&[bad]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    assert!(SmallModulus::new(1).is_err());
+    assert!(SmallModulus::new(1 << 62).is_err());
+    Ok(())
+}
+
+#[test]
+fn plain_modulus_as_small_modulus() -> Result<()> {
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 4096)?;
+    assert!(ep.plain_modulus()?.is_zero());
+
+    // A 40-bit prime congruent to 1 modulo 2 * 4096.
+    let t = SmallModulus::default_prime(40, 0)?;
+    ep.set_plain_modulus(t)?;
+    let plain_modulus = ep.plain_modulus()?;
+    assert_eq!(plain_modulus.value(), t.value());
+    assert_eq!(plain_modulus.bit_count(), 40);
+    assert!(plain_modulus.supports_batching(4096));
+
+    ep.set_plain_modulus_u64(1 << 20)?;
+    assert!(!ep.plain_modulus()?.supports_batching(4096));
+
+    let mut ckks = EncryptionParameters::new(SchemeType::Ckks)?;
+    match ckks.set_plain_modulus_u64(40961) {
+        Err(Error::LogicError(_)) => {}
+        other => panic!("expected LogicError, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+#[test]
+fn parameter_getters_and_parms_id() -> Result<()> {
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 4096)?;
+    ep.set_plain_modulus_u64(256)?;
+
+    assert_eq!(ep.scheme()?, SchemeType::Bfv);
+    assert_eq!(ep.poly_modulus_degree()?, 4096);
+    assert_eq!(ep.coeff_modulus_bit_count()?, 109);
+    assert!(ep.noise_standard_deviation()? > 3.0);
+    assert!(ep.noise_max_deviation()? > ep.noise_standard_deviation()?);
+
+    let mut same = EncryptionParameters::new(SchemeType::Bfv)?;
+    same.set_poly_modulus_degree(4096)?;
+    same.set_coeff_modulus_primes(&ep.coeff_modulus()?)?;
+    same.set_plain_modulus_u64(256)?;
+    assert_eq!(ep.parms_id()?, same.parms_id()?);
+    assert!(ep == same);
+
+    same.set_plain_modulus_u64(257)?;
+    assert!(ep != same);
+    assert_eq!(format!("{}", ParmsId::default()).len(), 4 * 16 + 3);
+    Ok(())
+}
 
-        let mut ct3 = bindings_Encryptor_encrypt(enc, pt1);
+#[test]
+fn builders_validate_parameters() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(40961)
+        .batching(true)
+        .build()?;
+    let kg = KeyGenerator::new(&ctx)?;
+    Encryptor::new(&ctx, &kg.public_key()?)?;
+
+    EncryptionParameters::ckks()
+        .poly_modulus_degree(8192)
+        .security_level(SecurityLevel::Tc192)
+        .build()?;
+
+    match EncryptionParameters::bfv().poly_modulus_degree(4096).build() {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        println!("Size of fresh encryption: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget in fresh encryption: {} bits", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+    // 256 is not congruent to 1 modulo 2 * 4096, so batching is unavailable.
+    let no_batching = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(256)
+        .batching(true)
+        .build();
+    match no_batching {
+        Err(Error::InvalidArgument(ref message)) => assert!(message.contains("using_batching")),
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        bindings_Evaluator_square_inplace(ev, ct3);
+    // Four 60-bit primes exceed the 128-bit security bound for degree 4096.
+    let too_large = EncryptionParameters::ckks()
+        .poly_modulus_degree(4096)
+        .coeff_modulus(&SmallModulus::default_primes(60, 4)?)
+        .build();
+    match too_large {
+        Err(Error::InvalidArgument(ref message)) => assert!(message.contains("using_he_std_security")),
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        println!("Size after squaring: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+    // Three 30-bit primes are within the 128-bit bound for degree 4096 but
+    // exceed the 192-bit one, while two are within both.
+    let too_large = EncryptionParameters::ckks()
+        .poly_modulus_degree(4096)
+        .security_level(SecurityLevel::Tc192)
+        .coeff_modulus(&SmallModulus::default_primes(30, 3)?)
+        .build();
+    match too_large {
+        Err(Error::InvalidArgument(ref message)) => assert!(message.contains("Tc192")),
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    EncryptionParameters::ckks()
+        .poly_modulus_degree(4096)
+        .security_level(SecurityLevel::Tc192)
+        .coeff_modulus(&SmallModulus::default_primes(30, 2)?)
+        .build()?;
+
+    // The standard has no bounds for degree 512.
+    let uncovered = EncryptionParameters::ckks()
+        .poly_modulus_degree(512)
+        .coeff_modulus(&SmallModulus::default_primes(30, 1)?)
+        .build();
+    match uncovered {
+        Err(Error::InvalidArgument(ref message)) => assert!(message.contains("512")),
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
 
-        bindings_Evaluator_relinearize_inplace(ev, ct3, rk60);
+#[test]
+fn context_qualifiers() -> Result<()> {
+    let mut ep = EncryptionParameters::new(SchemeType::Bfv)?;
+    ep.set_poly_modulus_degree(4096)?;
+    ep.set_coeff_modulus(SecurityLevel::Tc128, 4096)?;
+    ep.set_plain_modulus(SmallModulus::new(40961)?)?;
+    let ctx = Context::new(&ep, true)?;
+    assert!(ctx.parameters_set()?);
+    assert_eq!(
+        ctx.qualifiers()?,
+        EncryptionParameterQualifiers {
+            parameters_set: true,
+            using_fft: true,
+            using_ntt: true,
+            using_batching: true,
+            using_fast_plain_lift: true,
+            using_he_std_security: true,
+        }
+    );
+    assert_eq!(ctx.total_coeff_modulus_bit_count()?, ep.coeff_modulus_bit_count()?);
+
+    // A plain modulus larger than the coefficient modulus is rejected by
+    // SEAL without an exception, leaving the context unusable.
+    ep.set_poly_modulus_degree(1024)?;
+    ep.set_coeff_modulus_primes(&SmallModulus::default_primes(30, 1)?)?;
+    ep.set_plain_modulus(SmallModulus::default_prime(40, 0)?)?;
+    let ctx = Context::new(&ep, true)?;
+    assert!(!ctx.parameters_set()?);
+    assert!(!ctx.qualifiers()?.parameters_set);
+    Ok(())
+}
 
-        println!("Size after relinearization: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after relinearization (dbc = 60): {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+#[test]
+fn modulus_switching_chain() -> Result<()> {
+    let mut primes = SmallModulus::default_primes(60, 1)?;
+    primes.extend(SmallModulus::default_primes(40, 3)?);
+    let ctx = EncryptionParameters::ckks()
+        .poly_modulus_degree(8192)
+        .coeff_modulus(&primes)
+        .build()?;
+
+    let levels: Vec<ContextData> = ctx.chain()?.collect();
+    assert_eq!(levels.len(), 4);
+    assert_eq!(levels[0].parms_id(), ctx.first_parms_id()?);
+    assert_eq!(levels[3].parms_id(), ctx.last_parms_id()?);
+    for (i, level) in levels.iter().enumerate() {
+        assert_eq!(level.chain_index()?, i);
+        assert_eq!(level.coeff_modulus()?, primes[..4 - i].to_vec());
+    }
+    assert_eq!(levels[0].total_coeff_modulus_bit_count()?, 180);
+    assert_eq!(levels[3].total_coeff_modulus_bit_count()?, 60);
 
-        bindings_Evaluator_square_inplace(ev, ct3);
+    let last = ctx.last_context_data()?;
+    assert!(last.next()?.is_none());
+    assert_eq!(last.prev()?.map(|data| data.parms_id()), Some(levels[2].parms_id()));
+    assert!(ctx.first_context_data()?.prev()?.is_none());
 
-        println!("Size after second squaring: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after second squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+    match ctx.context_data(ParmsId::default()) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        bindings_Evaluator_relinearize_inplace(ev, ct3, rk60);
-        
-        println!("Size after relinearization: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after relinearization (dbc = 60): {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+    let unexpanded = EncryptionParameters::ckks()
+        .poly_modulus_degree(8192)
+        .coeff_modulus(&primes)
+        .expand_mod_chain(false)
+        .build()?;
+    assert_eq!(unexpanded.chain()?.len(), 1);
+    assert_eq!(unexpanded.first_parms_id()?, unexpanded.last_parms_id()?);
+    Ok(())
+}
 
-        let mut pt3 = bindings_Decryptor_decrypt(dec, ct3);
+#[test]
+fn plain_and_out_of_place_arithmetic() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(1 << 10)
+        .build()?;
+    let ie = IntegerEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let relin_keys = kg.relin_keys(16, 1)?;
+
+    let x = enc.encrypt(&ie.encode(7)?)?;
+    let y = enc.encrypt(&ie.encode(3)?)?;
+    let decode = |ct: &Ciphertext| -> Result<i32> { ie.decode_i32(&dec.decrypt(ct)?) };
+
+    assert_eq!(decode(&ev.add(&x, &y)?)?, 10);
+    assert_eq!(decode(&ev.sub(&x, &y)?)?, 4);
+    assert_eq!(decode(&ev.negate(&x)?)?, -7);
+    assert_eq!(decode(&ev.square(&y)?)?, 9);
+    let product = ev.multiply(&x, &y)?;
+    assert_eq!(product.size()?, 3);
+    let relinearized = ev.relinearize(&product, &relin_keys)?;
+    assert_eq!(relinearized.size()?, 2);
+    assert_eq!(decode(&relinearized)?, 21);
+
+    let five = ie.encode(5)?;
+    assert_eq!(decode(&ev.add_plain(&x, &five)?)?, 12);
+    assert_eq!(decode(&ev.sub_plain(&x, &five)?)?, 2);
+    assert_eq!(decode(&ev.multiply_plain(&x, &five)?)?, 35);
+
+    // The out-of-place forms leave their inputs untouched.
+    assert_eq!(decode(&x)?, 7);
+
+    let mut z = enc.encrypt(&ie.encode(7)?)?;
+    ev.sub_inplace(&mut z, &y)?;
+    ev.add_plain_inplace(&mut z, &five)?;
+    ev.multiply_plain_inplace(&mut z, &five)?;
+    ev.sub_plain_inplace(&mut z, &five)?;
+    assert_eq!(decode(&z)?, 40);
+    Ok(())
+}
 
-        println!("Fourth power: {}", CStr::from_ptr(bindings_Plaintext_to_string(pt3)).to_str().unwrap());
+#[test]
+fn add_many_multiply_many_exponentiate() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(8192)
+        .plain_modulus(1 << 16)
+        .build()?;
+    let ie = IntegerEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let relin_keys = kg.relin_keys(16, 1)?;
+
+    let encrypteds = (1..6)
+        .map(|i| enc.encrypt(&ie.encode(i)?))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(ie.decode_i32(&dec.decrypt(&ev.add_many(&encrypteds)?)?)?, 15);
+
+    let product = ev.multiply_many(&encrypteds, &relin_keys)?;
+    assert_eq!(product.size()?, 2);
+    assert_eq!(ie.decode_i32(&dec.decrypt(&product)?)?, 120);
+
+    let power = ev.exponentiate(&encrypteds[2], 4, &relin_keys)?;
+    assert_eq!(ie.decode_i32(&dec.decrypt(&power)?)?, 81);
+    let mut power = enc.encrypt(&ie.encode(2)?)?;
+    ev.exponentiate_inplace(&mut power, 5, &relin_keys)?;
+    assert_eq!(ie.decode_i32(&dec.decrypt(&power)?)?, 32);
+
+    match ev.add_many(&[]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match ev.exponentiate(&encrypteds[0], 0, &relin_keys) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
 
-        /*
-        Observe from the print-out that we have now used significantly more of our
-        noise budget than in the two previous runs. This is again not surprising, 
-        since the first relinearization chops off a huge part of the noise budget.
+#[test]
+fn batch_encoder_round_trips_slots() -> Result<()> {
+    let t = SmallModulus::new(40961)?;
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(t.value())
+        .build()?;
+    let be = BatchEncoder::new(&ctx)?;
+    assert_eq!(be.slot_count(), 4096);
+
+    let values: Vec<u64> = (0..100).map(|i| i * 400).collect();
+    let decoded = be.decode(&be.encode(&values)?)?;
+    assert_eq!(decoded.len(), 4096);
+    assert_eq!(&decoded[..100], &values[..]);
+    assert!(decoded[100..].iter().all(|&value| value == 0));
+
+    let signed = vec![-3, 2, -1, 0, (t.value() / 2) as i64];
+    assert_eq!(&be.decode_signed(&be.encode_signed(&signed)?)?[..5], &signed[..]);
+
+    match be.encode(&[t.value()]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match be.encode(&vec![0; 4097]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
 
-        However, note that the second relinearization does not change the noise
-        budget by any observable amount. This is very important to understand when
-        optimal performance is desired: relinearization always drops the noise
-        budget from the maximum (freshly encrypted ciphertext) down to a fixed 
-        amount depending on the encryption parameters and the decomposition bit 
-        count. On the other hand, homomorphic multiplication always consumes the
-        noise budget from its current level. This is why the second relinearization
-        does not change the noise budget anymore: it is already consumed past the
-        fixed amount determinted by the decomposition bit count and the encryption
-        parameters. 
+    let no_batching = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(256)
+        .build()?;
+    match BatchEncoder::new(&no_batching) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
 
-        We now perform a third squaring and observe an even further compounded
-        decrease in the noise budget. Again, relinearization does not consume the
-        noise budget at this point by any observable amount, even with the largest
-        possible decomposition bit count.
-        */
+#[test]
+fn slot_matrix_layout() -> Result<()> {
+    let mut matrix = SlotMatrix::from_rows(&[0, 1, 2, 3], &[4, 5, 6, 7])?;
+    assert_eq!(matrix.row_size(), 4);
+    assert_eq!(matrix.slot_count(), 8);
+    assert_eq!(matrix.row(1), &[4, 5, 6, 7]);
+    assert_eq!(matrix.column(2), [2, 6]);
+    matrix[(1, 0)] = 9;
+    assert_eq!(Vec::from(matrix), vec![0, 1, 2, 3, 9, 5, 6, 7]);
+
+    assert!(SlotMatrix::from_rows(&[1], &[1, 2]).is_err());
+    assert!(SlotMatrix::from_vec(vec![1, 2, 3]).is_err());
+
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(40961)
+        .build()?;
+    let be = BatchEncoder::new(&ctx)?;
+    let mut matrix = SlotMatrix::new(be.slot_count() / 2);
+    matrix.row_mut(0)[..4].copy_from_slice(&[-1, 2, -3, 4]);
+    matrix[(1, 2047)] = -5;
+    let decoded = be.decode_signed_matrix(&be.encode_signed_matrix(&matrix)?)?;
+    assert_eq!(decoded, matrix);
+    assert_eq!(be.decode(&be.encode_signed_matrix(&matrix)?)?[4095], 40956);
+
+    match be.encode_matrix(&SlotMatrix::new(4)) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
 
-        bindings_Evaluator_square_inplace(ev, ct3);
+#[test]
+fn ckks_encoder_round_trips_values() -> Result<()> {
+    let ctx = EncryptionParameters::ckks().poly_modulus_degree(8192).build()?;
+    let encoder = CkksEncoder::new(&ctx)?;
+    assert_eq!(encoder.slot_count(), 4096);
+    let parms_id = ctx.first_parms_id()?;
+    let scale = (1u64 << 40) as f64;
+
+    let values = [0.0, 1.1, -2.2, 3.3];
+    let decoded = encoder.decode(&encoder.encode_f64(&values, scale, parms_id)?)?;
+    assert_eq!(decoded.len(), 4096);
+    for (a, b) in values.iter().zip(&decoded) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    assert!(decoded[4..].iter().all(|value| value.abs() < 1e-6));
 
-        println!("Size after third squaring: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after third squaring: {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
+    let values = [Complex::new(1.0, -1.0), Complex::new(0.5, 2.0)];
+    let decoded = encoder.decode_complex(&encoder.encode_complex(&values, scale, parms_id)?)?;
+    for (a, b) in values.iter().zip(&decoded) {
+        assert!((a - b).norm() < 1e-6);
+    }
 
-        bindings_Evaluator_relinearize_inplace(ev, ct3, rk60);
-        
-        println!("Size after relinearization: {}", bindings_Ciphertext_size(ct3));
-        println!("Noise budget after relinearization (dbc = 60): {}", bindings_Decryptor_invariant_noise_budget(dec, ct3));
-
-        let mut pt3 = bindings_Decryptor_decrypt(dec, ct3);
-
-        println!("Eighth power: {}", CStr::from_ptr(bindings_Plaintext_to_string(pt3)).to_str().unwrap());
-
-        /*
-        Observe from the print-out that the polynomial coefficients are no longer
-        correct as integers: they have been reduced modulo plain_modulus, and there
-        was no warning sign about this. It might be necessary to carefully analyze
-        the computation to make sure such overflow does not occur unexpectedly.
-        These experiments suggest that an optimal strategy might be to relinearize
-        first with relinearization keys with a small decomposition bit count, and 
-        later with relinearization keys with a larger decomposition bit count (for 
-        performance) when noise budget has already been consumed past the bound 
-        determined by the larger decomposition bit count. For example, the best 
-        strategy might have been to use relin_keys16 in the first relinearization 
-        and relin_keys60 in the next two relinearizations for optimal noise budget 
-        consumption/performance trade-off. Luckily, in most use-cases it is not so 
-        critical to squeeze out every last bit of performance, especially when 
-        larger parameters are used.
-        */
+    let decoded = encoder.decode(&encoder.encode_scalar(1.75, scale, parms_id)?)?;
+    assert!(decoded.iter().all(|value| (value - 1.75).abs() < 1e-6));
+    let value = Complex::new(0.0, 1.0);
+    let decoded = encoder.decode_complex(&encoder.encode_complex_scalar(value, scale, parms_id)?)?;
+    assert!(decoded.iter().all(|c| (c - value).norm() < 1e-6));
+
+    // Encrypted values go through the same encoder.
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ct = enc.encrypt(&encoder.encode_f64(&[2.5], scale, parms_id)?)?;
+    assert!((encoder.decode(&dec.decrypt(&ct)?)?[0] - 2.5).abs() < 1e-4);
+
+    let bfv = EncryptionParameters::bfv().poly_modulus_degree(4096).plain_modulus(256).build()?;
+    match CkksEncoder::new(&bfv) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
     }
+    Ok(())
 }
+
 #[test]
-fn example_bfv_basics_iii() {
-    unsafe {
-        println!("Example: BFV Basics III");
-
-        /*
-        In this fundamental example we discuss and demonstrate a powerful technique 
-        called `batching'. If N denotes the degree of the polynomial modulus, and T
-        the plaintext modulus, then batching is automatically enabled for the BFV
-        scheme when T is a prime number congruent to 1 modulo 2*N. In batching the 
-        plaintexts are viewed as matrices of size 2-by-(N/2) with each element an 
-        integer modulo T. Homomorphic operations act element-wise between encrypted 
-        matrices, allowing the user to obtain speeds-ups of several orders of 
-        magnitude in naively vectorizable computations. We demonstrate two more 
-        homomorphic operations which act on encrypted matrices by rotating the rows 
-        cyclically, or rotate the columns (i.e. swap the rows). These operations 
-        require the construction of so-called `Galois keys', which are very similar 
-        to relinearization keys.
-        The batching functionality is totally optional in the BFV scheme and is 
-        exposed through the BatchEncoder class. 
-        */
-
-        let mut ep = bindings_EncryptionParameters_Create(1);
-        
-        bindings_EncryptionParameters_set_poly_modulus_degree(ep, 8192);
-        bindings_EncryptionParameters_set_coeff_modulus(ep, 128, 4096);
-
-        /*
-        Note that 40961 is a prime number and 2*4096 divides 40960, so batching will
-        automatically be enabled for these parameters.
-        */
-        bindings_EncryptionParameters_set_plain_modulus(ep, 40961);
-
-        let mut ctx = bindings_SEALContext_Create(ep, false);
-
-        /*
-        We can verify that batching is indeed enabled by looking at the encryption
-        parameter qualifiers created by SEALContext.
-        */
-        // TODO
-
-        let mut kg = bindings_KeyGenerator_Create(ctx);
-        let mut pk = bindings_KeyGenerator_public_key(kg);
-        let mut sk = bindings_KeyGenerator_secret_key(kg);
-
-        /*
-        We need to create so-called `Galois keys' for performing matrix row and 
-        column rotations on encrypted matrices. Like relinearization keys, the 
-        behavior of Galois keys depends on a decomposition bit count. The noise 
-        budget consumption behavior of matrix row and column rotations is exactly 
-        like that of relinearization (recall example_bfv_basics_ii()).
-        Here we use a moderate size decomposition bit count.
-        */
-        let mut gal_keys = bindings_KeyGenerator_galois_keys(kg, 30);
-
-        /*
-        Since we are going to do some multiplications we will also relinearize.
-        */
-        let mut relin_keys = bindings_KeyGenerator_relin_keys(kg, 30, 1);
-
-        /*
-        We also set up an Encryptor, Evaluator, and Decryptor here.
-        */
-        let mut enc = bindings_Encryptor_Create(ctx, pk);
-        let mut ev = bindings_Evaluator_Create(ctx);
-        let mut dec = bindings_Decryptor_Create(ctx, sk);
-
-        /*
-        Batching is done through an instance of the BatchEncoder class so need to
-        construct one.
-        */
-        let mut be = bindings_BatchEncoder_Create(ctx);
-
-        /*
-        The total number of batching `slots' is poly_modulus_degree. The matrices 
-        we encrypt are of size 2-by-(slot_count / 2).
-        */
-        println!("Plaintext matrix row size: {}", bindings_BatchEncoder_slot_count(be) / 2);
-
-        /*
-        Printing the matrix is a bit of a pain.
-        ... so I won't do it
-        */
-
-        /*
-        The matrix plaintext is simply given to BatchEncoder as a flattened vector
-        of numbers of size slot_count. The first row_size numbers form the first row, 
-        and the rest form the second row. Here we create the following matrix:
-            [ 0,  1,  2,  3,  0,  0, ...,  0 ]
-            [ 4,  5,  6,  7,  0,  0, ...,  0 ]
-        */
-        
-        // TODO
+fn integer_encoder_widths_and_bases() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(1 << 10)
+        .build()?;
+    let ie = IntegerEncoder::new(&ctx)?;
+    assert_eq!(ie.decode_i64(&ie.encode(-(1 << 40))?)?, -(1 << 40));
+    assert_eq!(ie.decode_u64(&ie.encode_u64(u64::MAX)?)?, u64::MAX);
+    let big = BigUInt::from_words(vec![7, 1 << 20]);
+    assert_eq!(ie.decode_biguint(&ie.encode_biguint(&big)?)?, big);
+    assert_eq!(format!("{}", big), "1000000000000000000007");
+    match ie.decode_i32(&ie.encode(1 << 40)?) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
     }
+    match ie.decode_u64(&ie.encode_biguint(&big)?) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    // In base 3 every digit is -1, 0 or 1: 5 = 9 - 3 - 1.
+    let ie3 = IntegerEncoder::with_base(&ctx, 3)?;
+    assert_eq!(ie3.encode(5)?.coeffs()?, vec![1023, 1023, 1]);
+    for &value in &[0, 1, -1, 12345, -987654321, i64::MAX, i64::MIN] {
+        assert_eq!(ie3.decode_i64(&ie3.encode(value)?)?, value);
+    }
+    assert_eq!(ie3.decode_biguint(&ie3.encode_biguint(&big)?)?, big);
+    match ie3.decode_u64(&ie3.encode(-1)?) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    // Arithmetic carries over to the encrypted values.
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let product = ev.multiply(&enc.encrypt(&ie3.encode(-42)?)?, &enc.encrypt(&ie3.encode(1000)?)?)?;
+    assert_eq!(ie3.decode_i64(&dec.decrypt(&product)?)?, -42000);
+
+    assert!(IntegerEncoder::with_base(&ctx, 1).is_err());
+    assert!(IntegerEncoder::with_base(&ctx, 1 << 10).is_err());
+    Ok(())
+}
+
+#[test]
+fn galois_keys_for_selected_steps() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(40961)
+        .batching(true)
+        .build()?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let be = BatchEncoder::new(&ctx)?;
+
+    assert_eq!(GaloisKeys::galois_elt_from_step(0, 4096)?, 8191);
+    assert_eq!(GaloisKeys::galois_elt_from_step(1, 4096)?, 3);
+    assert_eq!(GaloisKeys::galois_elt_from_step(2, 4096)?, 9);
+    assert!(GaloisKeys::galois_elt_from_step(2048, 4096).is_err());
+
+    let gal_keys = kg.galois_keys_for_steps(30, &[5, 0])?;
+    assert!(gal_keys.has_key(GaloisKeys::galois_elt_from_step(5, 4096)?)?);
+    assert!(gal_keys.has_key(8191)?);
+    assert!(!gal_keys.has_key(3)?);
+
+    let values: Vec<u64> = (0..4096).collect();
+    let encrypted = enc.encrypt(&be.encode(&values)?)?;
+    let rotated = be.decode(&dec.decrypt(&ev.rotate_rows(&encrypted, 5, &gal_keys)?)?)?;
+    assert_eq!(rotated[0], 5);
+    assert_eq!(rotated[2047], 4);
+    assert_eq!(rotated[2048], 2053);
+    let swapped = be.decode(&dec.decrypt(&ev.rotate_columns(&encrypted, &gal_keys)?)?)?;
+    assert_eq!(swapped[0], 2048);
+    assert_eq!(swapped[2048], 0);
+
+    let elt = GaloisKeys::galois_elt_from_step(5, 4096)?;
+    let applied = be.decode(&dec.decrypt(&ev.apply_galois(&encrypted, elt, &gal_keys)?)?)?;
+    assert_eq!(applied, rotated);
+
+    match ev.rotate_rows(&encrypted, 1, &gal_keys) {
+        Err(Error::InvalidArgument(message)) => assert!(message.contains("galois element 3"), "{}", message),
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match ev.apply_galois(&encrypted, 3, &gal_keys) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    let elt_keys = kg.galois_keys_for_elts(30, &[3])?;
+    assert!(elt_keys.has_key(3)?);
+    assert_eq!(be.decode(&dec.decrypt(&ev.rotate_rows(&encrypted, 1, &elt_keys)?)?)?[0], 1);
+    Ok(())
+}
+
+#[test]
+fn relin_keys_powers_and_partial_relinearization() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(1 << 10)
+        .build()?;
+    let ie = IntegerEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+
+    for &(dbc, count) in &[(KeyGenerator::dbc_min()? - 1, 1), (KeyGenerator::dbc_max()? + 1, 1), (30, 0)] {
+        match kg.relin_keys(dbc, count) {
+            Err(Error::InvalidArgument(_)) => {}
+            other => panic!("expected InvalidArgument, got {:?}", other.err()),
+        }
+    }
+    let relin_keys = kg.relin_keys(30, 2)?;
+    assert_eq!(relin_keys.size()?, 2);
+    assert_eq!(relin_keys.decomposition_bit_count()?, 30);
+    assert_eq!(relin_keys.parms_id()?, ctx.first_parms_id()?);
+
+    let encrypted = enc.encrypt(&ie.encode(3)?)?;
+    let cubed = ev.multiply(&ev.square(&encrypted)?, &encrypted)?;
+    assert_eq!(cubed.size()?, 4);
+    for destination_size in 2..5 {
+        let relinearized = ev.relinearize_to(&cubed, &relin_keys, destination_size)?;
+        assert_eq!(relinearized.size()?, destination_size);
+        assert_eq!(ie.decode_i64(&dec.decrypt(&relinearized)?)?, 27);
+    }
+    match ev.relinearize_to(&cubed, &relin_keys, 1) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match ev.relinearize_to(&cubed, &kg.relin_keys(30, 1)?, 2) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+#[test]
+fn modulus_switching_and_rescaling() -> Result<()> {
+    let ctx = EncryptionParameters::ckks()
+        .poly_modulus_degree(8192)
+        .coeff_modulus(&SmallModulus::default_primes(40, 4)?)
+        .build()?;
+    let levels: Vec<ParmsId> = ctx.chain()?.map(|data| data.parms_id()).collect();
+    let encoder = CkksEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let relin_keys = kg.relin_keys(30, 1)?;
+    let scale = (1u64 << 40) as f64;
+    let encrypt = |value: f64, scale: f64| enc.encrypt(&encoder.encode_scalar(value, scale, levels[0])?);
+    let decrypt = |encrypted: &Ciphertext| -> Result<f64> { Ok(encoder.decode(&dec.decrypt(encrypted)?)?[0]) };
+
+    let encrypted = encrypt(1.5, scale)?;
+    assert_eq!(encrypted.parms_id()?, levels[0]);
+    assert_eq!(encrypted.scale()?, scale);
+    let switched = ev.mod_switch_to_next(&encrypted)?;
+    assert_eq!(switched.parms_id()?, levels[1]);
+    assert_eq!(switched.scale()?, scale);
+    assert!((decrypt(&switched)? - 1.5).abs() < 1e-4);
+    let switched = ev.mod_switch_to(&encrypted, levels[3])?;
+    assert_eq!(switched.parms_id()?, levels[3]);
+    match ev.mod_switch_to(&switched, levels[0]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    let mut plain = encoder.encode_scalar(2.0, scale, levels[0])?;
+    assert_eq!(plain.parms_id()?, levels[0]);
+    assert_eq!(plain.scale()?, scale);
+    ev.mod_switch_plain_to_inplace(&mut plain, levels[2])?;
+    assert_eq!(plain.parms_id()?, levels[2]);
+    assert_eq!(ev.mod_switch_plain_to_next(&plain)?.parms_id()?, levels[3]);
+
+    let mut product = ev.multiply(&encrypted, &encrypt(2.0, scale)?)?;
+    ev.relinearize_inplace(&mut product, &relin_keys)?;
+    let rescaled = ev.rescale_to_next(&product)?;
+    assert_eq!(rescaled.parms_id()?, levels[1]);
+    assert!((rescaled.scale()? / scale - 1.0).abs() < 1e-3);
+    assert!((decrypt(&rescaled)? - 3.0).abs() < 1e-3);
+    assert_eq!(ev.rescale_to(&product, levels[2])?.parms_id()?, levels[2]);
+
+    // The product is at scale 2^80 on the first level, so it cannot be added
+    // to a fresh ciphertext until both are aligned.
+    // Rescaling leaves the product's scale close to but not equal to the
+    // addend's, so aligning them exactly fails without changing either.
+    let mut addend = encrypt(0.5, scale)?;
+    assert!(ev.add(&product, &addend).is_err());
+    match ev.align(&mut product, &mut addend, 0.0) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    assert_eq!(product.parms_id()?, levels[0]);
+    assert_eq!(addend.scale()?, scale);
+    ev.align(&mut product, &mut addend, 1e-3)?;
+    assert_eq!(product.parms_id()?, levels[1]);
+    assert_eq!(addend.parms_id()?, levels[1]);
+    assert_eq!(product.scale()?, addend.scale()?);
+    assert!((decrypt(&ev.add(&product, &addend)?)? - 3.5).abs() < 1e-2);
+
+    let mut low = ev.mod_switch_to(&encrypt(1.0, scale)?, levels[2])?;
+    let mut high = encrypt(2.0, scale)?;
+    ev.align_levels(&mut high, &mut low)?;
+    assert_eq!(high.parms_id()?, levels[2]);
+    assert_eq!(low.parms_id()?, levels[2]);
+
+    let mut small = encrypt(1.0, (1u64 << 20) as f64)?;
+    match ev.align(&mut high, &mut small, 1e-3) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    assert_eq!(small.parms_id()?, levels[0]);
+    Ok(())
+}
+
+#[test]
+fn ntt_transforms_and_precomputed_plaintexts() -> Result<()> {
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(4096)
+        .plain_modulus(40961)
+        .build()?;
+    let be = BatchEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let ev = Evaluator::new(&ctx)?;
+    let parms_id = ctx.first_parms_id()?;
+
+    let weights: Vec<u64> = (0..4096).map(|i| i % 7 + 1).collect();
+    let plain = be.encode(&weights)?;
+    assert!(!plain.is_ntt_form()?);
+    let weights_ntt = ev.transform_plain_to_ntt(&plain, parms_id)?;
+    assert!(weights_ntt.is_ntt_form()?);
+    assert_eq!(weights_ntt.parms_id()?, parms_id);
+
+    let inputs: Vec<u64> = (0..4096).map(|i| i % 100).collect();
+    let expected: Vec<u64> = inputs.iter().zip(&weights).map(|(x, w)| x * w).collect();
+    for _ in 0..3 {
+        let encrypted = enc.encrypt(&be.encode(&inputs)?)?;
+        assert!(!encrypted.is_ntt_form()?);
+        let product = ev.multiply_plain(&encrypted, &weights_ntt)?;
+        assert!(!product.is_ntt_form()?);
+        assert_eq!(be.decode(&dec.decrypt(&product)?)?, expected);
+    }
+
+    let mut encrypted = enc.encrypt(&be.encode(&inputs)?)?;
+    ev.transform_to_ntt_inplace(&mut encrypted)?;
+    assert!(encrypted.is_ntt_form()?);
+    ev.multiply_plain_inplace(&mut encrypted, &weights_ntt)?;
+    assert!(encrypted.is_ntt_form()?);
+    match ev.multiply_plain(&encrypted, &plain) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    let encrypted = ev.transform_from_ntt(&encrypted)?;
+    assert_eq!(be.decode(&dec.decrypt(&encrypted)?)?, expected);
+
+    let mut plain = plain;
+    ev.transform_plain_to_ntt_inplace(&mut plain, parms_id)?;
+    let encrypted = ev.mod_switch_to_next(&enc.encrypt(&be.encode(&inputs)?)?)?;
+    match ev.multiply_plain(&encrypted, &plain) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+/// A BFV context with batching at n = 4096 and t = 40961, and the tools for
+/// one key pair, shared by the serialization and symmetric encryption tests.
+#[cfg(test)]
+struct BatchingSetup {
+    ctx: Context,
+    be: BatchEncoder,
+    kg: KeyGenerator,
+    enc: Encryptor,
+    dec: Decryptor,
+    ev: Evaluator,
+}
+
+#[cfg(test)]
+impl BatchingSetup {
+    fn new() -> Result<BatchingSetup> {
+        let ctx = EncryptionParameters::bfv()
+            .poly_modulus_degree(4096)
+            .plain_modulus(40961)
+            .build()?;
+        let be = BatchEncoder::new(&ctx)?;
+        let kg = KeyGenerator::new(&ctx)?;
+        let enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+        let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+        let ev = Evaluator::new(&ctx)?;
+        Ok(BatchingSetup { ctx, be, kg, enc, dec, ev })
+    }
+}
+
+#[test]
+fn save_and_load_round_trip() -> Result<()> {
+    let BatchingSetup { ctx, be, kg, enc, .. } = BatchingSetup::new()?;
+    let values: Vec<u64> = (0..4096).map(|i| i * 3).collect();
+    let plain = be.encode(&values)?;
+    let encrypted = enc.encrypt(&plain)?;
+
+    let mut stream = Vec::new();
+    ctx.first_context_data()?.parms()?.save(&mut stream)?;
+    encrypted.save(&mut stream)?;
+    plain.save(&mut stream)?;
+    kg.public_key()?.save(&mut stream)?;
+    kg.secret_key()?.save(&mut stream)?;
+    kg.relin_keys(30, 2)?.save(&mut stream)?;
+    kg.galois_keys_for_steps(30, &[1])?.save(&mut stream)?;
+
+    let mut reader = &stream[..];
+    let parms = EncryptionParameters::load(&mut reader)?;
+    assert_eq!(parms, ctx.first_context_data()?.parms()?);
+    let ctx = Context::new(&parms, true)?;
+    let encrypted = Ciphertext::load(&ctx, &mut reader)?;
+    let plain = Plaintext::load(&ctx, &mut reader)?;
+    let public_key = PublicKey::load(&ctx, &mut reader)?;
+    let secret_key = SecretKey::load(&ctx, &mut reader)?;
+    let relin_keys = RelinKeys::load(&ctx, &mut reader)?;
+    let galois_keys = GaloisKeys::load(&ctx, &mut reader)?;
+    assert!(reader.is_empty());
+
+    let dec = Decryptor::new(&ctx, &secret_key)?;
+    assert_eq!(be.decode(&dec.decrypt(&encrypted)?)?, values);
+    assert_eq!(be.decode(&plain)?, values);
+    assert_eq!(relin_keys.size()?, 2);
+    assert!(galois_keys.has_key(3)?);
+    let fresh = Encryptor::new(&ctx, &public_key)?.encrypt(&plain)?;
+    assert_eq!(be.decode(&dec.decrypt(&fresh)?)?, values);
+
+    // A ciphertext only loads into a context with the parameters it was
+    // created with, and a truncated stream is an I/O error.
+    let mut saved = Vec::new();
+    encrypted.save(&mut saved)?;
+    let other = EncryptionParameters::bfv()
+        .poly_modulus_degree(8192)
+        .plain_modulus(40961)
+        .build()?;
+    match Ciphertext::load(&other, &mut &saved[..]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match Ciphertext::load(&ctx, &mut &saved[..saved.len() - 1]) {
+        Err(Error::Io(_)) => {}
+        other => panic!("expected Io, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+#[test]
+fn envelopes_describe_and_guard_their_payload() -> Result<()> {
+    let BatchingSetup { ctx, be, enc, ev, .. } = BatchingSetup::new()?;
+    let values: Vec<u64> = (0..4096).map(|i| i % 100).collect();
+    let encrypted = enc.encrypt(&be.encode(&values)?)?;
+
+    let mut saved = Vec::new();
+    encrypted.save(&mut saved)?;
+    assert_eq!(&saved[..4], &envelope::MAGIC);
+    let header = envelope::Header::read(&mut &saved[..])?;
+    assert_eq!(header.version, envelope::FORMAT_VERSION);
+    assert_eq!(header.kind, envelope::ObjectKind::Ciphertext);
+    assert_eq!(header.scheme, SchemeType::Bfv);
+    assert_eq!(header.parms_id, ctx.first_parms_id()?);
+    assert_eq!(header.compression, envelope::Compression::None);
+    assert_eq!(header.payload_len as usize, saved.len() - envelope::HEADER_SIZE);
+    assert_eq!(envelope::crc32(&[b"1234", b"56789"]), 0xcbf4_3926);
+
+    // Lower levels of the chain load into the same context.
+    let mut switched = Vec::new();
+    ev.mod_switch_to_next(&encrypted)?.save(&mut switched)?;
+    let loaded = Ciphertext::load(&ctx, &mut &switched[..])?;
+    assert_eq!(loaded.parms_id()?, ctx.last_parms_id()?);
+
+    // The wrong kind or scheme is rejected from the header alone.
+    match Plaintext::load(&ctx, &mut &saved[..]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    let ckks = EncryptionParameters::ckks().poly_modulus_degree(4096).build()?;
+    match Ciphertext::load(&ckks, &mut &saved[..]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+
+    // Corruption and foreign data are I/O errors.
+    let mut corrupted = saved.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    match Ciphertext::load(&ctx, &mut &corrupted[..]) {
+        Err(Error::Io(_)) => {}
+        other => panic!("expected Io, got {:?}", other.err()),
+    }
+    // The checksum covers the header too, so a corrupted header field is
+    // caught even where its new value is valid.
+    let mut corrupted = saved.clone();
+    corrupted[40] = envelope::Compression::Deflate as u8;
+    match Ciphertext::load(&ctx, &mut &corrupted[..]) {
+        Err(Error::Io(ref message)) => assert!(message.contains("checksum")),
+        other => panic!("expected Io, got {:?}", other.err()),
+    }
+    let mut foreign = saved.clone();
+    foreign[0] = b'X';
+    match Ciphertext::load(&ctx, &mut &foreign[..]) {
+        Err(Error::Io(_)) => {}
+        other => panic!("expected Io, got {:?}", other.err()),
+    }
+    let mut newer = saved.clone();
+    newer[4] = (envelope::FORMAT_VERSION + 1) as u8;
+    match Ciphertext::load(&ctx, &mut &newer[..]) {
+        Err(Error::Io(_)) => {}
+        other => panic!("expected Io, got {:?}", other.err()),
+    }
+    Ok(())
+}
+
+#[test]
+fn seeded_symmetric_encryption() -> Result<()> {
+    let BatchingSetup { ctx, be, kg, enc: public_enc, dec, ev } = BatchingSetup::new()?;
+    let values: Vec<u64> = (0..4096).map(|i| i * 5 % 40961).collect();
+    let plain = be.encode(&values)?;
+
+    // The client encrypts with its secret key and uploads half the bytes.
+    let enc = Encryptor::with_secret_key(&ctx, &kg.secret_key()?)?;
+    let seeded = enc.encrypt_seeded(&plain)?;
+    assert_eq!(seeded.parms_id(), ctx.first_parms_id()?);
+    let mut upload = Vec::new();
+    seeded.save(&mut upload)?;
+    let mut full = Vec::new();
+    seeded.expand()?.save(&mut full)?;
+    assert!(upload.len() * 10 < full.len() * 6);
+
+    // The server expands it into an ordinary ciphertext.
+    let encrypted = Ciphertext::load_seeded(&ctx, &mut &upload[..])?;
+    assert_eq!(encrypted.size()?, 2);
+    assert!(dec.invariant_noise_budget(&encrypted)? > 0);
+    assert_eq!(be.decode(&dec.decrypt(&encrypted)?)?, values);
+    let public = public_enc.encrypt(&plain)?;
+    let doubled: Vec<u64> = values.iter().map(|v| v * 2 % 40961).collect();
+    assert_eq!(be.decode(&dec.decrypt(&ev.add(&encrypted, &public)?)?)?, doubled);
+    assert_eq!(be.decode(&dec.decrypt(&enc.encrypt(&plain)?)?)?, values);
+
+    // The noise of a symmetric encryption is a single error term, so it never
+    // leaves less noise budget than a public key encryption, which adds more.
+    let public_budget = dec.invariant_noise_budget(&public)?;
+    for _ in 0..8 {
+        assert!(dec.invariant_noise_budget(&enc.encrypt(&plain)?)? >= public_budget);
+        assert!(dec.invariant_noise_budget(&enc.encrypt_seeded(&plain)?.expand()?)? >= public_budget);
+    }
+
+    // A seeded ciphertext is not a ciphertext.
+    match Ciphertext::load(&ctx, &mut &upload[..]) {
+        Err(Error::InvalidArgument(_)) => {}
+        other => panic!("expected InvalidArgument, got {:?}", other.err()),
+    }
+    match public_enc.encrypt_seeded(&plain) {
+        Err(Error::LogicError(_)) => {}
+        other => panic!("expected LogicError, got {:?}", other.err()),
+    }
+
+    // CKKS ciphertexts stay in NTT form and keep the level and scale of the
+    // plaintext, at every level of the chain. Their error is pinned well below
+    // what the values need, and they mix with public key encryptions.
+    let ctx = EncryptionParameters::ckks().poly_modulus_degree(8192).build()?;
+    let encoder = CkksEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let ev = Evaluator::new(&ctx)?;
+    let dec = Decryptor::new(&ctx, &kg.secret_key()?)?;
+    let enc = Encryptor::with_secret_key(&ctx, &kg.secret_key()?)?;
+    let public_enc = Encryptor::new(&ctx, &kg.public_key()?)?;
+    let scale = (1u64 << 40) as f64;
+    for data in ctx.chain()? {
+        let parms_id = data.parms_id();
+        let plain = encoder.encode_f64(&[1.5, -2.25], scale, parms_id)?;
+        let seeded = enc.encrypt_seeded(&plain)?;
+        assert_eq!(seeded.parms_id(), parms_id);
+        assert_eq!(seeded.scale(), scale);
+        let mut upload = Vec::new();
+        seeded.save(&mut upload)?;
+        let encrypted = Ciphertext::load_seeded(&ctx, &mut &upload[..])?;
+        assert!(encrypted.is_ntt_form()?);
+        assert_eq!(encrypted.parms_id()?, parms_id);
+        assert_eq!(encrypted.scale()?, scale);
+        let decoded = encoder.decode(&dec.decrypt(&encrypted)?)?;
+        assert!((decoded[0] - 1.5).abs() < 1e-6);
+        assert!((decoded[1] + 2.25).abs() < 1e-6);
+
+        let direct = enc.encrypt(&plain)?;
+        assert_eq!(direct.parms_id()?, parms_id);
+        assert_eq!(direct.scale()?, scale);
+        let sum = ev.add(&direct, &public_enc.encrypt(&plain)?)?;
+        let decoded = encoder.decode(&dec.decrypt(&sum)?)?;
+        assert!((decoded[0] - 3.0).abs() < 1e-4);
+        assert!((decoded[1] + 4.5).abs() < 1e-4);
+    }
+    Ok(())
+}
+
+#[test]
+fn seed_expansion_known_answers() {
+    use sha3::digest::XofReader;
+
+    // FIPS 202 example values for SHAKE-128 of the empty message and of 200
+    // bytes 0xA3.
+    let expected: [(Vec<u8>, &str); 2] = [
+        (vec![], "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"),
+        (vec![0xa3; 200], "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037"),
+    ];
+    for (input, digest) in expected.iter() {
+        let mut output = [0; 32];
+        XofReader::read(&mut ciphertext::shake128(input), &mut output);
+        let hex: String = output.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, *digest);
+    }
+
+    // Half of the words are rejected for the second prime, which is just above
+    // 2^63.
+    let poly = ciphertext::uniform_poly(&[1, 2, 3, 4], &[97, (1 << 63) + 1], 4);
+    assert_eq!(
+        poly,
+        [67, 24, 12, 52, 525187717139785046, 3799349365347473376, 888723814335765489, 2254682918137488544]
+    );
+}
+
+#[test]
+fn seeded_ciphertexts_expand_to_the_encryption() -> Result<()> {
+    let BatchingSetup { ctx, kg, .. } = BatchingSetup::new()?;
+    let enc = Encryptor::with_secret_key(&ctx, &kg.secret_key()?)?;
+    let (encrypted, seed) = enc.encrypt_symmetric(&Plaintext::from_hex_poly("1x^1 + 2")?)?;
+    let expanded = SeededCiphertext::from_ciphertext(&encrypted, seed)?.expand()?;
+    assert!(!expanded.is_ntt_form()?);
+    assert_eq!(expanded.parms_id()?, encrypted.parms_id()?);
+    assert_eq!(expanded.poly(0)?, encrypted.poly(0)?);
+    assert_eq!(expanded.poly(1)?, encrypted.poly(1)?);
+
+    // CKKS ciphertexts are in NTT form, in which the second polynomial is the
+    // expansion of the seed itself.
+    let ctx = EncryptionParameters::ckks().poly_modulus_degree(8192).build()?;
+    let encoder = CkksEncoder::new(&ctx)?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let enc = Encryptor::with_secret_key(&ctx, &kg.secret_key()?)?;
+    let scale = (1u64 << 40) as f64;
+    for data in ctx.chain()? {
+        let plain = encoder.encode_f64(&[1.5, -2.25], scale, data.parms_id())?;
+        let (encrypted, seed) = enc.encrypt_symmetric(&plain)?;
+        assert_eq!(encrypted.poly(1)?, ciphertext::expand_seed(&data, &seed)?);
+        let expanded = SeededCiphertext::from_ciphertext(&encrypted, seed)?.expand()?;
+        assert!(expanded.is_ntt_form()?);
+        assert_eq!(expanded.parms_id()?, data.parms_id());
+        assert_eq!(expanded.scale()?, scale);
+        assert_eq!(expanded.poly(0)?, encrypted.poly(0)?);
+        assert_eq!(expanded.poly(1)?, encrypted.poly(1)?);
+    }
+    Ok(())
+}
+
+/// Returns the compressions this build supports, `Compression::None` first.
+#[cfg(test)]
+fn available_compressions() -> Vec<envelope::Compression> {
+    use envelope::Compression;
+
+    vec![Compression::None, Compression::Zstd, Compression::Deflate]
+        .into_iter()
+        .filter(|compression| compression.is_available())
+        .collect()
+}
+
+#[test]
+fn compressed_ciphertexts_and_keys_round_trip() -> Result<()> {
+    let BatchingSetup { ctx, be, kg, enc, dec, .. } = BatchingSetup::new()?;
+    let values: Vec<u64> = (0..4096).map(|i| i * 7 % 40961).collect();
+    let encrypted = enc.encrypt(&be.encode(&values)?)?;
+    let relin_keys = kg.relin_keys(30, 1)?;
+    let galois_keys = kg.galois_keys_for_steps(30, &[1, -1])?;
+
+    let mut raw = Vec::new();
+    encrypted.save(&mut raw)?;
+    for compression in available_compressions() {
+        let mut saved = Vec::new();
+        encrypted.save_compressed(&mut saved, compression)?;
+        assert_eq!(envelope::Header::read(&mut &saved[..])?.compression, compression);
+        if compression != envelope::Compression::None {
+            // The default BFV primes leave the top bits of every word zero.
+            assert!(saved.len() < raw.len());
+        }
+        let loaded = Ciphertext::load(&ctx, &mut &saved[..])?;
+        assert_eq!(be.decode(&dec.decrypt(&loaded)?)?, values);
+
+        let mut saved = Vec::new();
+        relin_keys.save_compressed(&mut saved, compression)?;
+        galois_keys.save_compressed(&mut saved, compression)?;
+        let mut reader = &saved[..];
+        assert_eq!(RelinKeys::load(&ctx, &mut reader)?.size()?, 1);
+        assert!(GaloisKeys::load(&ctx, &mut reader)?.has_key(GaloisKeys::galois_elt_from_step(-1, 4096)?)?);
+        assert!(reader.is_empty());
+    }
+
+    // A payload that decompresses to more than any ciphertext of the context
+    // is rejected before it is fully inflated, and so is a Galois key stream
+    // longer than the single key its start declares.
+    let limit = envelope::decompressed_limit(&ctx, envelope::ObjectKind::Ciphertext, &[])?;
+    let ciphertext_bomb = vec![0; limit as usize + 1];
+    let mut keys_bomb = vec![0; 32];
+    keys_bomb.extend_from_slice(&60i32.to_le_bytes());
+    keys_bomb.extend_from_slice(&1u64.to_le_bytes());
+    let limit = envelope::decompressed_limit(&ctx, envelope::ObjectKind::GaloisKeys, &keys_bomb)?;
+    assert!(limit < 1 << 20);
+    keys_bomb.resize(limit as usize + 1, 0);
+    for compression in available_compressions() {
+        if compression != envelope::Compression::None {
+            let parms_id = encrypted.parms_id()?;
+            let mut saved = Vec::new();
+            let kind = envelope::ObjectKind::Ciphertext;
+            envelope::write(&mut saved, kind, SchemeType::Bfv, parms_id, compression, &ciphertext_bomb)?;
+            assert!(saved.len() < ciphertext_bomb.len() / 100);
+            match Ciphertext::load(&ctx, &mut &saved[..]) {
+                Err(Error::Io(_)) => {}
+                other => panic!("expected Io, got {:?}", other.err()),
+            }
+
+            let mut saved = Vec::new();
+            let kind = envelope::ObjectKind::GaloisKeys;
+            envelope::write(&mut saved, kind, SchemeType::Bfv, parms_id, compression, &keys_bomb)?;
+            match GaloisKeys::load(&ctx, &mut &saved[..]) {
+                Err(Error::Io(_)) => {}
+                other => panic!("expected Io, got {:?}", other.err()),
+            }
+        }
+    }
+
+    // A build without a codec refuses to write or read it.
+    for &compression in &[envelope::Compression::Zstd, envelope::Compression::Deflate] {
+        if !compression.is_available() {
+            match encrypted.save_compressed(&mut Vec::new(), compression) {
+                Err(Error::InvalidArgument(_)) => {}
+                other => panic!("expected InvalidArgument, got {:?}", other.err()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints the size and timings of each available compression for the largest
+/// objects: full Galois keys and a ciphertext at n = 16384. Run with
+/// `cargo test --release --all-features -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn compression_size_and_speed() -> Result<()> {
+    use std::time::Instant;
+
+    let ctx = EncryptionParameters::bfv()
+        .poly_modulus_degree(16384)
+        .plain_modulus(786433)
+        .build()?;
+    let kg = KeyGenerator::new(&ctx)?;
+    let encrypted = Encryptor::new(&ctx, &kg.public_key()?)?.encrypt(&Plaintext::from_hex_poly("1x^1 + 2")?)?;
+    let galois_keys = kg.galois_keys(60)?;
+
+    // `available_compressions` lists `Compression::None` first.
+    let (mut raw_keys_size, mut raw_ciphertext_size) = (0, 0);
+    for compression in available_compressions() {
+        let start = Instant::now();
+        let mut saved = Vec::new();
+        galois_keys.save_compressed(&mut saved, compression)?;
+        let save_time = start.elapsed();
+        if compression == envelope::Compression::None {
+            raw_keys_size = saved.len();
+        } else {
+            assert!(saved.len() < raw_keys_size);
+        }
+        let start = Instant::now();
+        GaloisKeys::load(&ctx, &mut &saved[..])?;
+        let load_time = start.elapsed();
+        println!(
+            "galois keys, {:?}: {} bytes, saved in {:?}, loaded in {:?}",
+            compression,
+            saved.len(),
+            save_time,
+            load_time
+        );
+
+        let start = Instant::now();
+        let mut saved = Vec::new();
+        encrypted.save_compressed(&mut saved, compression)?;
+        let save_time = start.elapsed();
+        if compression == envelope::Compression::None {
+            raw_ciphertext_size = saved.len();
+        } else {
+            assert!(saved.len() < raw_ciphertext_size);
+        }
+        let start = Instant::now();
+        Ciphertext::load(&ctx, &mut &saved[..])?;
+        let load_time = start.elapsed();
+        println!(
+            "ciphertext, {:?}: {} bytes, saved in {:?}, loaded in {:?}",
+            compression,
+            saved.len(),
+            save_time,
+            load_time
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_with_context_seed() -> Result<()> {
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    let BatchingSetup { ctx, be, kg, enc, dec, .. } = BatchingSetup::new()?;
+    let values: Vec<u64> = (0..4096).map(|i| i % 1000).collect();
+    let encrypted = enc.encrypt(&be.encode(&values)?)?;
+
+    // Human-readable formats get base64 strings.
+    let json = serde_json::to_string(&encrypted).unwrap();
+    assert!(json.starts_with('"'));
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let decoded: Ciphertext = ContextSeed::new(&ctx).deserialize(&mut de).unwrap();
+    assert_eq!(be.decode(&dec.decrypt(&decoded)?)?, values);
+
+    // Binary formats get the raw bytes.
+    let bytes = bincode::options().serialize(&encrypted).unwrap();
+    assert!(bytes.len() < json.len());
+    let mut de = bincode::Deserializer::from_slice(&bytes, bincode::options());
+    let decoded: Ciphertext = ContextSeed::new(&ctx).deserialize(&mut de).unwrap();
+    assert_eq!(be.decode(&dec.decrypt(&decoded)?)?, values);
+
+    let json = serde_json::to_string(&kg.secret_key()?).unwrap();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let secret_key: SecretKey = ContextSeed::new(&ctx).deserialize(&mut de).unwrap();
+    assert_eq!(be.decode(&Decryptor::new(&ctx, &secret_key)?.decrypt(&encrypted)?)?, values);
+
+    let parms = ctx.first_context_data()?.parms()?;
+    let decoded: EncryptionParameters = serde_json::from_str(&serde_json::to_string(&parms).unwrap()).unwrap();
+    assert_eq!(decoded, parms);
+    let parms_id = ctx.first_parms_id()?;
+    let decoded: ParmsId = bincode::deserialize(&bincode::serialize(&parms_id).unwrap()).unwrap();
+    assert_eq!(decoded, parms_id);
+
+    // Deserialization checks the object against the context.
+    let other = EncryptionParameters::bfv()
+        .poly_modulus_degree(8192)
+        .plain_modulus(40961)
+        .build()?;
+    let json = serde_json::to_string(&encrypted).unwrap();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert!(ContextSeed::<Ciphertext>::new(&other).deserialize(&mut de).is_err());
+    Ok(())
 }
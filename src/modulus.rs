@@ -0,0 +1,117 @@
+use error::{check, Error, Result};
+use ffi;
+
+/// A modulus of at most 62 bits, as used for the factors of the coefficient
+/// modulus and for the plain modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SmallModulus {
+    value: u64,
+}
+
+impl SmallModulus {
+    /// Creates a modulus with the given value, which must be zero or between
+    /// 2 and 2^62 - 1, matching the range SEAL accepts.
+    pub fn new(value: u64) -> Result<SmallModulus> {
+        if value == 1 || value >> 62 != 0 {
+            return Err(Error::InvalidArgument(format!(
+                "{} is not a valid SmallModulus: it must be 0 or have between 2 and 62 bits",
+                value
+            )));
+        }
+        Ok(SmallModulus { value })
+    }
+
+    /// Returns one of SEAL's pre-generated NTT-friendly primes from the
+    /// `DefaultParams::small_mods_*bit` tables.
+    ///
+    /// `bit_size` selects the table and must be 30, 40, 50 or 60; `index` must
+    /// be below 64. Every prime in these tables is congruent to 1 modulo
+    /// 2 * 32768, so it can be used with any supported polynomial modulus
+    /// degree.
+    pub fn default_prime(bit_size: u32, index: usize) -> Result<SmallModulus> {
+        let mut value = 0;
+        check(unsafe { ffi::bindings_DefaultParams_small_mods(bit_size as i32, index, &mut value) })?;
+        Ok(SmallModulus { value })
+    }
+
+    /// Returns the first `count` primes of the given bit size from SEAL's
+    /// tables, e.g. to build a CKKS modulus chain.
+    pub fn default_primes(bit_size: u32, count: usize) -> Result<Vec<SmallModulus>> {
+        (0..count).map(|i| SmallModulus::default_prime(bit_size, i)).collect()
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the number of significant bits of the modulus.
+    pub fn bit_count(&self) -> u32 {
+        64 - self.value.leading_zeros()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Returns whether the modulus is prime, using a Miller-Rabin test with a
+    /// set of bases that is deterministic for all 64-bit values.
+    pub fn is_prime(&self) -> bool {
+        let n = self.value;
+        if n < 2 {
+            return false;
+        }
+        const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+        if BASES.contains(&n) {
+            return true;
+        }
+
+        // n - 1 = d * 2^r with d odd
+        let r = (n - 1).trailing_zeros();
+        let d = (n - 1) >> r;
+        'bases: for &a in BASES.iter() {
+            let mut x = pow_mod(a, d, n);
+            if x == 1 || x == n - 1 {
+                continue;
+            }
+            for _ in 1..r {
+                x = mul_mod(x, x, n);
+                if x == n - 1 {
+                    continue 'bases;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    /// Returns whether this modulus, used as the plain modulus, enables
+    /// batching for the given polynomial modulus degree: it must be a prime
+    /// congruent to 1 modulo 2 * poly_modulus_degree.
+    pub fn supports_batching(&self, poly_modulus_degree: usize) -> bool {
+        let two_n = 2 * poly_modulus_degree as u64;
+        two_n != 0 && self.value % two_n == 1 && self.is_prime()
+    }
+}
+
+impl From<SmallModulus> for u64 {
+    fn from(modulus: SmallModulus) -> u64 {
+        modulus.value
+    }
+}
+
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(n)) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, n: u64) -> u64 {
+    let mut result = 1;
+    base %= n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    result
+}
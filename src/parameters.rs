@@ -0,0 +1,253 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::ptr;
+
+use builder::{BfvParametersBuilder, CkksParametersBuilder};
+use envelope::{self, Compression, Header, ObjectKind};
+use error::{check, Error, Result};
+use ffi;
+use modulus::SmallModulus;
+use serialization;
+
+/// The homomorphic encryption scheme used by a set of encryption parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemeType {
+    Bfv = 1,
+    Ckks = 2,
+}
+
+impl SchemeType {
+    pub(crate) fn from_raw(value: i32) -> Result<SchemeType> {
+        match value {
+            1 => Ok(SchemeType::Bfv),
+            2 => Ok(SchemeType::Ckks),
+            _ => Err(Error::Unknown(format!("unsupported scheme type {}", value))),
+        }
+    }
+}
+
+/// The security level targeted by SEAL's default coefficient moduli, following
+/// the HomomorphicEncryption.org security standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecurityLevel {
+    Tc128 = 128,
+    Tc192 = 192,
+    Tc256 = 256,
+}
+
+/// The hash SEAL uses to identify a set of encryption parameters, and each
+/// level of a context's modulus switching chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ParmsId([u64; 4]);
+
+impl ParmsId {
+    pub fn new(words: [u64; 4]) -> ParmsId {
+        ParmsId(words)
+    }
+
+    pub fn words(&self) -> [u64; 4] {
+        self.0
+    }
+}
+
+impl From<[u64; 4]> for ParmsId {
+    fn from(words: [u64; 4]) -> ParmsId {
+        ParmsId(words)
+    }
+}
+
+impl fmt::Display for ParmsId {
+    /// Formats the id the way SEAL prints it: four zero-padded hex words
+    /// separated by spaces.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x} {:016x} {:016x} {:016x}", self.0[0], self.0[1], self.0[2], self.0[3])
+    }
+}
+
+/// Owned wrapper around SEAL's `EncryptionParameters`.
+pub struct EncryptionParameters {
+    ptr: *mut ffi::seal_EncryptionParameters,
+}
+
+impl EncryptionParameters {
+    /// Starts building BFV parameters, exposing only the settings BFV uses.
+    pub fn bfv() -> BfvParametersBuilder {
+        BfvParametersBuilder::new()
+    }
+
+    /// Starts building CKKS parameters, exposing only the settings CKKS uses.
+    pub fn ckks() -> CkksParametersBuilder {
+        CkksParametersBuilder::new()
+    }
+
+    /// Creates an empty set of encryption parameters for the given scheme.
+    pub fn new(scheme: SchemeType) -> Result<EncryptionParameters> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_EncryptionParameters_Create(scheme as i32, &mut ptr) })?;
+        Ok(EncryptionParameters { ptr })
+    }
+
+    pub fn set_poly_modulus_degree(&mut self, degree: i32) -> Result<()> {
+        check(unsafe { ffi::bindings_EncryptionParameters_set_poly_modulus_degree(self.ptr, degree) })
+    }
+
+    /// Sets the coefficient modulus to SEAL's default for the given security
+    /// level and polynomial modulus degree.
+    ///
+    /// Defaults only exist for degrees 1024, 2048, 4096, 8192, 16384 and
+    /// 32768; any other degree returns `Error::OutOfRange`.
+    pub fn set_coeff_modulus(&mut self, security_level: SecurityLevel, degree: i32) -> Result<()> {
+        check(unsafe {
+            ffi::bindings_EncryptionParameters_set_coeff_modulus(self.ptr, security_level as i32, degree)
+        })
+    }
+
+    /// Sets the coefficient modulus to the product of the given primes.
+    ///
+    /// The polynomial modulus degree must already be set, and every prime must
+    /// be congruent to 1 modulo 2 * poly_modulus_degree so that SEAL can use
+    /// the NTT with it; `SmallModulus::default_primes` returns primes that
+    /// satisfy this.
+    pub fn set_coeff_modulus_primes(&mut self, primes: &[SmallModulus]) -> Result<()> {
+        let values: Vec<u64> = primes.iter().map(|p| p.value()).collect();
+        check(unsafe {
+            ffi::bindings_EncryptionParameters_set_coeff_modulus_primes(self.ptr, values.as_ptr(), values.len())
+        })
+    }
+
+    /// Sets the plain modulus. Only the BFV scheme has one; on CKKS parameters
+    /// this returns `Error::LogicError`.
+    pub fn set_plain_modulus(&mut self, modulus: SmallModulus) -> Result<()> {
+        check(unsafe { ffi::bindings_EncryptionParameters_set_plain_modulus(self.ptr, modulus.value()) })
+    }
+
+    /// Same as `set_plain_modulus`, checking first that `modulus` is a valid
+    /// `SmallModulus`.
+    pub fn set_plain_modulus_u64(&mut self, modulus: u64) -> Result<()> {
+        self.set_plain_modulus(SmallModulus::new(modulus)?)
+    }
+
+    /// Returns the plain modulus, which is zero until it has been set.
+    pub fn plain_modulus(&self) -> Result<SmallModulus> {
+        let mut value = 0;
+        check(unsafe { ffi::bindings_EncryptionParameters_plain_modulus(self.ptr, &mut value) })?;
+        SmallModulus::new(value)
+    }
+
+    pub fn scheme(&self) -> Result<SchemeType> {
+        let mut value = 0;
+        check(unsafe { ffi::bindings_EncryptionParameters_scheme(self.ptr, &mut value) })?;
+        SchemeType::from_raw(value)
+    }
+
+    /// Returns the polynomial modulus degree, which is zero until it has been
+    /// set.
+    pub fn poly_modulus_degree(&self) -> Result<usize> {
+        let mut degree = 0;
+        check(unsafe { ffi::bindings_EncryptionParameters_poly_modulus_degree(self.ptr, &mut degree) })?;
+        Ok(degree)
+    }
+
+    /// Returns the primes making up the coefficient modulus, in the order
+    /// they were set.
+    pub fn coeff_modulus(&self) -> Result<Vec<SmallModulus>> {
+        let mut count = 0;
+        check(unsafe {
+            ffi::bindings_EncryptionParameters_coeff_modulus(self.ptr, ptr::null_mut(), &mut count)
+        })?;
+        let mut values = vec![0; count];
+        check(unsafe {
+            ffi::bindings_EncryptionParameters_coeff_modulus(self.ptr, values.as_mut_ptr(), &mut count)
+        })?;
+        values.into_iter().map(SmallModulus::new).collect()
+    }
+
+    /// Returns the sum of the bit counts of the coefficient modulus primes.
+    pub fn coeff_modulus_bit_count(&self) -> Result<u32> {
+        Ok(self.coeff_modulus()?.iter().map(|p| p.bit_count()).sum())
+    }
+
+    /// Returns the standard deviation of the noise distribution.
+    pub fn noise_standard_deviation(&self) -> Result<f64> {
+        let mut value = 0.0;
+        check(unsafe { ffi::bindings_EncryptionParameters_noise_standard_deviation(self.ptr, &mut value) })?;
+        Ok(value)
+    }
+
+    /// Returns the bound at which the noise distribution is truncated.
+    pub fn noise_max_deviation(&self) -> Result<f64> {
+        let mut value = 0.0;
+        check(unsafe { ffi::bindings_EncryptionParameters_noise_max_deviation(self.ptr, &mut value) })?;
+        Ok(value)
+    }
+
+    /// Returns the hash SEAL computes over the scheme, degree and moduli.
+    /// Two parameter sets with the same parms_id are interchangeable.
+    pub fn parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_EncryptionParameters_parms_id(self.ptr, words.as_mut_ptr()) })?;
+        Ok(ParmsId(words))
+    }
+
+    /// Writes the parameters to `writer` in SEAL's binary format, wrapped in
+    /// an envelope.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        let (kind, compression) = (ObjectKind::EncryptionParameters, Compression::None);
+        envelope::write(writer, kind, self.scheme()?, self.parms_id()?, compression, &self.to_bytes()?)
+    }
+
+    /// Reads parameters written by `save`. They are not validated until a
+    /// `Context` is created for them.
+    pub fn load(reader: &mut impl Read) -> Result<EncryptionParameters> {
+        let header = Header::read(reader)?;
+        header.check_kind(ObjectKind::EncryptionParameters)?;
+        let limit = |_: &[u8]| Ok(envelope::MAX_PARAMETERS_SIZE);
+        EncryptionParameters::from_bytes(&envelope::read_payload(reader, &header, &limit)?)
+    }
+
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_EncryptionParameters_save(self.ptr, data, size) })
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<EncryptionParameters> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_EncryptionParameters_load(bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(EncryptionParameters::from_ptr(ptr))
+    }
+
+    pub(crate) fn from_ptr(ptr: *mut ffi::seal_EncryptionParameters) -> EncryptionParameters {
+        EncryptionParameters { ptr }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_EncryptionParameters {
+        self.ptr
+    }
+}
+
+impl PartialEq for EncryptionParameters {
+    /// Compares parms_ids, as SEAL's `operator==` does.
+    fn eq(&self, other: &EncryptionParameters) -> bool {
+        match (self.parms_id(), other.parms_id()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for EncryptionParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EncryptionParameters")
+            .field("scheme", &self.scheme().ok())
+            .field("poly_modulus_degree", &self.poly_modulus_degree().ok())
+            .field("coeff_modulus", &self.coeff_modulus().ok())
+            .field("plain_modulus", &self.plain_modulus().ok())
+            .field("parms_id", &self.parms_id().ok())
+            .finish()
+    }
+}
+
+impl Drop for EncryptionParameters {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_EncryptionParameters_Destroy(self.ptr) }
+    }
+}
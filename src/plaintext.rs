@@ -0,0 +1,138 @@
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::io::{Read, Write};
+use std::ptr;
+
+use context::Context;
+use envelope::ObjectKind;
+use error::{check, Error, Result};
+use ffi;
+use parameters::{ParmsId, SchemeType};
+use serialization::{self, Serializable};
+
+/// Owned wrapper around SEAL's `Plaintext` polynomial.
+pub struct Plaintext {
+    /// The context of the encoder or evaluator that produced the plaintext;
+    /// `None` for plaintexts built directly from coefficients.
+    ctx: Option<Context>,
+    ptr: *mut ffi::seal_Plaintext,
+}
+
+impl Plaintext {
+    /// Creates a plaintext from a polynomial written in hexadecimal, such as
+    /// `"1x^2 + 2x^1 + 3"`.
+    pub fn from_hex_poly(hex_poly: &str) -> Result<Plaintext> {
+        let hex_poly = CString::new(hex_poly)
+            .map_err(|_| Error::InvalidArgument("hex_poly contains a nul byte".to_owned()))?;
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Plaintext_Create(hex_poly.as_ptr(), &mut ptr) })?;
+        Ok(Plaintext { ctx: None, ptr })
+    }
+
+    /// Creates a plaintext from its coefficients, constant term first.
+    pub fn from_coeffs(coeffs: &[u64]) -> Result<Plaintext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Plaintext_Create_coeffs(coeffs.as_ptr(), coeffs.len(), &mut ptr) })?;
+        Ok(Plaintext { ctx: None, ptr })
+    }
+
+    /// Returns the coefficients of the polynomial, constant term first.
+    pub fn coeffs(&self) -> Result<Vec<u64>> {
+        let mut count = 0;
+        check(unsafe { ffi::bindings_Plaintext_coeffs(self.ptr, ptr::null_mut(), &mut count) })?;
+        let mut coeffs = vec![0; count];
+        check(unsafe { ffi::bindings_Plaintext_coeffs(self.ptr, coeffs.as_mut_ptr(), &mut count) })?;
+        Ok(coeffs)
+    }
+
+    /// Returns the polynomial written in hexadecimal, in the format accepted
+    /// by `from_hex_poly`.
+    pub fn to_hex_poly(&self) -> Result<String> {
+        let mut raw = ptr::null_mut();
+        check(unsafe { ffi::bindings_Plaintext_to_string(self.ptr, &mut raw) })?;
+        unsafe {
+            let hex_poly = CStr::from_ptr(raw).to_string_lossy().into_owned();
+            ffi::bindings_String_Destroy(raw);
+            Ok(hex_poly)
+        }
+    }
+
+    /// Returns the parms_id of a plaintext in NTT form, such as a CKKS
+    /// plaintext. Plaintexts not in NTT form have the zero parms_id.
+    pub fn parms_id(&self) -> Result<ParmsId> {
+        let mut words = [0; 4];
+        check(unsafe { ffi::bindings_Plaintext_parms_id(self.ptr, words.as_mut_ptr()) })?;
+        Ok(ParmsId::new(words))
+    }
+
+    /// Returns the scale of a CKKS plaintext.
+    pub fn scale(&self) -> Result<f64> {
+        let mut scale = 0.0;
+        check(unsafe { ffi::bindings_Plaintext_scale(self.ptr, &mut scale) })?;
+        Ok(scale)
+    }
+
+    /// Returns whether the plaintext is in NTT form, as CKKS plaintexts and
+    /// plaintexts passed through `Evaluator::transform_plain_to_ntt` are.
+    pub fn is_ntt_form(&self) -> Result<bool> {
+        let mut is_ntt_form = false;
+        check(unsafe { ffi::bindings_Plaintext_is_ntt_form(self.ptr, &mut is_ntt_form) })?;
+        Ok(is_ntt_form)
+    }
+
+    /// Writes the plaintext to `writer` in SEAL's binary format, wrapped in
+    /// an envelope recording its scheme and parms_id.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        serialization::save(self, writer)
+    }
+
+    /// Reads a plaintext written by `save`, checking that it is valid for
+    /// `ctx`.
+    pub fn load(ctx: &Context, reader: &mut impl Read) -> Result<Plaintext> {
+        serialization::load(ctx, reader)
+    }
+
+    pub(crate) fn from_ptr(ctx: &Context, ptr: *mut ffi::seal_Plaintext) -> Plaintext {
+        Plaintext { ctx: Some(ctx.clone()), ptr }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ffi::seal_Plaintext {
+        self.ptr
+    }
+}
+
+impl fmt::Display for Plaintext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex_poly().map_err(|_| fmt::Error)?)
+    }
+}
+
+impl Serializable for Plaintext {
+    const KIND: ObjectKind = ObjectKind::Plaintext;
+
+    /// Plaintexts built from coefficients have no context and are recorded as
+    /// BFV plaintexts, the only scheme they can be used with.
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)> {
+        let scheme = match self.ctx {
+            Some(ref ctx) => serialization::context_scheme(ctx)?,
+            None => SchemeType::Bfv,
+        };
+        Ok((scheme, self.parms_id()?))
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialization::save_bytes(|data, size| unsafe { ffi::bindings_Plaintext_save(self.ptr, data, size) })
+    }
+
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<Plaintext> {
+        let mut ptr = ptr::null_mut();
+        check(unsafe { ffi::bindings_Plaintext_load(ctx.as_ptr(), bytes.as_ptr(), bytes.len(), &mut ptr) })?;
+        Ok(Plaintext::from_ptr(ctx, ptr))
+    }
+}
+
+impl Drop for Plaintext {
+    fn drop(&mut self) {
+        unsafe { ffi::bindings_Plaintext_Destroy(self.ptr) }
+    }
+}
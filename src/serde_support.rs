@@ -0,0 +1,160 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use base64;
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use serde::Deserialize;
+
+use ciphertext::{Ciphertext, SeededCiphertext};
+use context::Context;
+use keys::{GaloisKeys, PublicKey, RelinKeys, SecretKey};
+use parameters::{EncryptionParameters, ParmsId};
+use plaintext::Plaintext;
+use serialization::{self, Serializable};
+
+/// Deserializes a ciphertext, plaintext or key, checking that it is valid for
+/// a context before returning it.
+///
+/// All of these objects serialize as the bytes `save` writes, envelope
+/// included.
+///
+/// SEAL needs the context to read these objects, so they do not implement
+/// `Deserialize` themselves; pass a seed to `DeserializeSeed::deserialize`
+/// instead, e.g. `ContextSeed::<Ciphertext>::new(&ctx).deserialize(&mut de)`.
+pub struct ContextSeed<'a, T> {
+    ctx: &'a Context,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> ContextSeed<'a, T> {
+    pub fn new(ctx: &'a Context) -> ContextSeed<'a, T> {
+        ContextSeed { ctx, marker: PhantomData }
+    }
+}
+
+impl<'a, T> Clone for ContextSeed<'a, T> {
+    fn clone(&self) -> ContextSeed<'a, T> {
+        *self
+    }
+}
+
+impl<'a, T> Copy for ContextSeed<'a, T> {}
+
+impl<'de, 'a, T: Serializable> DeserializeSeed<'de> for ContextSeed<'a, T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        serialization::load(self.ctx, &mut &bytes[..]).map_err(de::Error::custom)
+    }
+}
+
+macro_rules! impl_serialize {
+    ($($t:ty),*) => {
+        $(
+            impl Serialize for $t {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut bytes = Vec::new();
+                    self.save(&mut bytes).map_err(ser::Error::custom)?;
+                    serialize_bytes(&bytes, serializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_serialize!(
+    Ciphertext,
+    SeededCiphertext,
+    Plaintext,
+    PublicKey,
+    SecretKey,
+    RelinKeys,
+    GaloisKeys,
+    EncryptionParameters
+);
+
+/// Encryption parameters are checked when a `Context` is created for them, so
+/// they deserialize without a seed.
+impl<'de> Deserialize<'de> for EncryptionParameters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<EncryptionParameters, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        EncryptionParameters::load(&mut &bytes[..]).map_err(de::Error::custom)
+    }
+}
+
+/// Serialized as its four words in little-endian byte order.
+impl Serialize for ParmsId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::with_capacity(32);
+        for word in &self.words() {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        serialize_bytes(&bytes, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ParmsId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ParmsId, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        if bytes.len() != 32 {
+            return Err(de::Error::invalid_length(bytes.len(), &"32 bytes"));
+        }
+        let mut words = [0; 4];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            let mut word_bytes = [0; 8];
+            word_bytes.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(word_bytes);
+        }
+        Ok(ParmsId::new(words))
+    }
+}
+
+/// Writes bytes as a base64 string to human-readable formats such as JSON,
+/// and as a byte string to binary ones.
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&base64::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a byte string or a base64 string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        base64::decode(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
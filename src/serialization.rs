@@ -0,0 +1,80 @@
+use std::io::{self, Read, Write};
+use std::ptr;
+use std::slice;
+
+use libc::c_int;
+
+use context::Context;
+use envelope::{self, Compression, Header, ObjectKind};
+use error::{check, Error, Result};
+use ffi;
+use parameters::{ParmsId, SchemeType};
+
+/// A SEAL object that is serialized as a SEAL stream and checked against a
+/// context when read back.
+///
+/// This is public only so that it can bound the generic serialization
+/// helpers; it cannot be named outside the crate.
+pub(crate) trait Serializable: Sized {
+    /// The kind recorded in the envelope header.
+    const KIND: ObjectKind;
+
+    /// Returns the scheme and parms_id recorded in the envelope header.
+    fn header_fields(&self) -> Result<(SchemeType, ParmsId)>;
+
+    /// Returns the SEAL stream of the object.
+    fn to_bytes(&self) -> Result<Vec<u8>>;
+
+    /// Reads the object from a SEAL stream, checking that it is valid for
+    /// `ctx`.
+    fn from_bytes(ctx: &Context, bytes: &[u8]) -> Result<Self>;
+}
+
+/// Writes the SEAL stream of `object` to `writer` in an envelope.
+pub(crate) fn save<T: Serializable, W: Write + ?Sized>(object: &T, writer: &mut W) -> Result<()> {
+    save_compressed(object, writer, Compression::None)
+}
+
+/// Writes the SEAL stream of `object` to `writer` in an envelope, compressed
+/// with `compression`. `load` decompresses it transparently.
+pub(crate) fn save_compressed<T: Serializable, W: Write + ?Sized>(
+    object: &T,
+    writer: &mut W,
+    compression: Compression,
+) -> Result<()> {
+    let (scheme, parms_id) = object.header_fields()?;
+    envelope::write(writer, T::KIND, scheme, parms_id, compression, &object.to_bytes()?)
+}
+
+/// Reads an object written by `save`, checking the envelope header against
+/// `ctx` before handing the payload to SEAL.
+pub(crate) fn load<T: Serializable, R: Read + ?Sized>(ctx: &Context, reader: &mut R) -> Result<T> {
+    let header = Header::read(reader)?;
+    header.check_kind(T::KIND)?;
+    header.check_context(ctx)?;
+    let limit = |prefix: &[u8]| envelope::decompressed_limit(ctx, T::KIND, prefix);
+    T::from_bytes(ctx, &envelope::read_payload(reader, &header, &limit)?)
+}
+
+/// Returns the scheme of the parameters `ctx` was created with.
+pub(crate) fn context_scheme(ctx: &Context) -> Result<SchemeType> {
+    ctx.first_context_data()?.parms()?.scheme()
+}
+
+/// Returns the SEAL stream written by the shim function `save` into a buffer
+/// it allocates.
+pub(crate) fn save_bytes<F>(save: F) -> Result<Vec<u8>>
+where
+    F: FnOnce(*mut *mut u8, *mut usize) -> c_int,
+{
+    let mut data = ptr::null_mut();
+    let mut size = 0;
+    check(save(&mut data, &mut size))?;
+    let bytes = unsafe { slice::from_raw_parts(data, size) }.to_vec();
+    unsafe { ffi::bindings_Bytes_Destroy(data) };
+    Ok(bytes)
+}
+
+pub(crate) fn io_error(error: io::Error) -> Error {
+    Error::Io(error.to_string())
+}
@@ -0,0 +1,115 @@
+use std::ops::{Index, IndexMut};
+
+use error::{Error, Result};
+
+/// The 2-by-(slot_count / 2) matrix view of the slots of a batched
+/// plaintext.
+///
+/// BFV rotations act on this layout: `rotate_rows` cycles both rows at once,
+/// and `rotate_columns` swaps the two rows. The values are stored row by row,
+/// which is the order `BatchEncoder` uses for flat slot vectors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SlotMatrix<T> {
+    row_size: usize,
+    values: Vec<T>,
+}
+
+impl<T: Clone + Default> SlotMatrix<T> {
+    /// Creates a matrix of two rows of `row_size` default values.
+    pub fn new(row_size: usize) -> SlotMatrix<T> {
+        SlotMatrix { row_size, values: vec![T::default(); 2 * row_size] }
+    }
+}
+
+impl<T: Clone> SlotMatrix<T> {
+    /// Creates a matrix from its two rows, which must have the same length.
+    pub fn from_rows(row0: &[T], row1: &[T]) -> Result<SlotMatrix<T>> {
+        if row0.len() != row1.len() {
+            return Err(Error::InvalidArgument(format!(
+                "rows have different lengths {} and {}",
+                row0.len(),
+                row1.len()
+            )));
+        }
+        let mut values = Vec::with_capacity(2 * row0.len());
+        values.extend_from_slice(row0);
+        values.extend_from_slice(row1);
+        Ok(SlotMatrix { row_size: row0.len(), values })
+    }
+
+    /// Returns the values of column `column`, top row first.
+    ///
+    /// Panics if `column` is not smaller than `row_size()`.
+    pub fn column(&self, column: usize) -> [T; 2] {
+        [self[(0, column)].clone(), self[(1, column)].clone()]
+    }
+}
+
+impl<T> SlotMatrix<T> {
+    /// Creates a matrix from its values row by row. The length of `values`
+    /// must be even.
+    pub fn from_vec(values: Vec<T>) -> Result<SlotMatrix<T>> {
+        if values.len() & 1 == 1 {
+            return Err(Error::InvalidArgument(format!(
+                "{} values do not form two rows",
+                values.len()
+            )));
+        }
+        Ok(SlotMatrix { row_size: values.len() / 2, values })
+    }
+
+    /// Returns the number of columns.
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    /// Returns the number of values, which is twice `row_size()`.
+    pub fn slot_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns row `row`, which must be 0 or 1.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < 2, "row index {} out of range", row);
+        &self.values[row * self.row_size..(row + 1) * self.row_size]
+    }
+
+    /// Returns row `row` mutably, which must be 0 or 1.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < 2, "row index {} out of range", row);
+        let row_size = self.row_size;
+        &mut self.values[row * row_size..(row + 1) * row_size]
+    }
+
+    /// Returns the values row by row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the values row by row.
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T> Index<(usize, usize)> for SlotMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        assert!(column < self.row_size, "column index {} out of range", column);
+        &self.row(row)[column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for SlotMatrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        assert!(column < self.row_size, "column index {} out of range", column);
+        &mut self.row_mut(row)[column]
+    }
+}
+
+impl<T> From<SlotMatrix<T>> for Vec<T> {
+    fn from(matrix: SlotMatrix<T>) -> Vec<T> {
+        matrix.values
+    }
+}